@@ -0,0 +1,86 @@
+use std::path::PathBuf;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use polars::prelude::*;
+use rsfake::extract::{read_file, write_dataframe, CsvOptions, OutputOptions};
+use rsfake::generate::{create_series_from_type, RowSeeds};
+use serde_json::json;
+
+const ROWS: usize = 100_000;
+
+/// Each output format with the extension it's written under.
+const FORMATS: &[(&str, &str)] = &[
+    ("parquet", "parquet"),
+    ("json", "json"),
+    ("jsonl", "jsonl"),
+    ("csv", "csv"),
+    ("tsv", "tsv"),
+    ("ipc_stream", "arrows"),
+    ("xlsx", "xlsx"),
+];
+
+fn sample_dataframe() -> DataFrame {
+    let columns = [
+        ("id", "u32"),
+        ("score", "f64"),
+        ("active", "Boolean"),
+        ("name", "Name"),
+        ("email", "SafeEmail"),
+        ("tier", "Choice"),
+        ("created", "DateTime"),
+    ]
+    .iter()
+    .map(|(name, type_name)| {
+        let col_def = json!({ "name": name, "type": type_name, "args": { "ratio": 50, "values": ["a", "b", "c"] } });
+        create_series_from_type(type_name, name, &RowSeeds::Random(ROWS), &col_def).unwrap()
+    })
+    .collect();
+    DataFrame::new(columns).unwrap()
+}
+
+fn bench_path(extension: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join("rsfake-bench");
+    std::fs::create_dir_all(&dir).unwrap();
+    dir.join(format!("bench.{}", extension))
+}
+
+fn bench_writers(c: &mut Criterion) {
+    let mut df = sample_dataframe();
+    df.as_single_chunk_par();
+    let options = OutputOptions::default();
+
+    let mut group = c.benchmark_group("write");
+    group.throughput(Throughput::Elements(ROWS as u64));
+    group.sample_size(10);
+    for (format, extension) in FORMATS {
+        let path = bench_path(extension);
+        let path = path.to_str().unwrap();
+        group.bench_with_input(BenchmarkId::from_parameter(format), format, |b, format| {
+            b.iter(|| write_dataframe(&mut df.clone(), path, format, &options).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn bench_readers(c: &mut Criterion) {
+    let mut df = sample_dataframe();
+    let options = OutputOptions::default();
+    let csv_options = CsvOptions::default();
+
+    let mut group = c.benchmark_group("read");
+    group.throughput(Throughput::Elements(ROWS as u64));
+    group.sample_size(10);
+    // There is no xlsx reader.
+    for (format, extension) in FORMATS.iter().filter(|(format, _)| *format != "xlsx") {
+        let path = bench_path(extension);
+        let path = path.to_str().unwrap();
+        write_dataframe(&mut df, path, format, &options).unwrap();
+        group.bench_with_input(BenchmarkId::from_parameter(format), path, |b, path| {
+            b.iter(|| read_file(path, &csv_options).unwrap())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_writers, bench_readers);
+criterion_main!(benches);
@@ -0,0 +1,97 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use fake::faker::address::raw::ZipCode;
+use fake::faker::company::raw::CompanyName;
+use fake::faker::internet::raw::SafeEmail;
+use fake::faker::lorem::raw::{Sentence, Word};
+use fake::faker::name::raw::{FirstName, Name};
+use fake::locales::EN;
+use fake::Fake;
+use polars::prelude::{NamedFrom, Series};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use rsfake::generate::{create_series_from_type, RowSeeds};
+use rsfake::Registry;
+use serde_json::json;
+
+const ROWS: usize = 100_000;
+
+/// Thread counts to compare, capped at the machine's parallelism.
+fn thread_counts() -> Vec<usize> {
+    let max = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let mut counts: Vec<usize> = [1, 2, 4, 8].into_iter().filter(|&n| n < max).collect();
+    counts.push(max);
+    counts
+}
+
+fn bench_types(c: &mut Criterion) {
+    // One set of args that satisfies every type that requires some.
+    let args = json!({
+        "ratio": 50,
+        "values": ["bronze", "silver", "gold"],
+        "fmt": "###-####",
+        "precision": 8,
+        "dt": "2024-01-01T00:00:00Z",
+        "start": "2020-01-01T00:00:00Z",
+        "end": "2024-01-01T00:00:00Z",
+    });
+    let pools: Vec<(usize, rayon::ThreadPool)> = thread_counts()
+        .into_iter()
+        .map(|n| (n, rayon::ThreadPoolBuilder::new().num_threads(n).build().unwrap()))
+        .collect();
+
+    // Every built-in type enabled by the current cargo features.
+    for type_name in Registry::default().type_names() {
+        let col_def = json!({ "name": "c", "type": type_name, "args": args });
+        let seeds = RowSeeds::Random(ROWS);
+
+        let mut group = c.benchmark_group(format!("generate/{}", type_name));
+        group.throughput(Throughput::Elements(ROWS as u64));
+        group.sample_size(10);
+        for (threads, pool) in &pools {
+            group.bench_with_input(BenchmarkId::new("threads", threads), pool, |b, pool| {
+                b.iter(|| pool.install(|| create_series_from_type(type_name, "c", &seeds, &col_def).unwrap()))
+            });
+        }
+        group.finish();
+    }
+}
+
+/// Compares string columns built into Arrow string builders with the path
+/// they took before: one `String` per row, collected into a `Vec` and then
+/// copied into a Series. Both run on one thread.
+fn bench_string_baseline(c: &mut Criterion) {
+    type Faker = fn(&mut StdRng) -> String;
+    let fakers: [(&str, Faker); 7] = [
+        ("Word", |rng| Word(EN).fake_with_rng::<&str, _>(rng).to_string()),
+        ("FirstName", |rng| FirstName(EN).fake_with_rng::<&str, _>(rng).to_string()),
+        ("Name", |rng| Name(EN).fake_with_rng(rng)),
+        ("SafeEmail", |rng| SafeEmail(EN).fake_with_rng(rng)),
+        ("ZipCode", |rng| ZipCode(EN).fake_with_rng(rng)),
+        ("CompanyName", |rng| CompanyName(EN).fake_with_rng(rng)),
+        ("Sentence", |rng| Sentence(EN, 3..10).fake_with_rng(rng)),
+    ];
+    let pool = rayon::ThreadPoolBuilder::new().num_threads(1).build().unwrap();
+
+    for (type_name, faker) in fakers {
+        let col_def = json!({ "name": "c", "type": type_name });
+        let seeds = RowSeeds::Random(ROWS);
+
+        let mut group = c.benchmark_group(format!("string_path/{}", type_name));
+        group.throughput(Throughput::Elements(ROWS as u64));
+        group.sample_size(10);
+        group.bench_function("vec_string", |b| {
+            b.iter(|| {
+                let mut rng = StdRng::from_entropy();
+                let values: Vec<String> = (0..ROWS).map(|_| faker(&mut rng)).collect();
+                Series::new("c", values)
+            })
+        });
+        group.bench_function("builder", |b| {
+            b.iter(|| pool.install(|| create_series_from_type(type_name, "c", &seeds, &col_def).unwrap()))
+        });
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_types, bench_string_baseline);
+criterion_main!(benches);
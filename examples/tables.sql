@@ -0,0 +1,21 @@
+-- Customers and their orders, as created in Postgres.
+CREATE TABLE customers (
+    id SERIAL PRIMARY KEY,
+    email VARCHAR(255) NOT NULL UNIQUE,
+    first_name VARCHAR(50) NOT NULL,
+    last_name VARCHAR(50),
+    signup_date DATE NOT NULL,
+    loyalty_points SMALLINT NOT NULL,
+    is_active BOOLEAN NOT NULL
+);
+
+CREATE TABLE orders (
+    id BIGSERIAL,
+    customer_id INTEGER NOT NULL REFERENCES customers (id),
+    status VARCHAR(20) NOT NULL,
+    total NUMERIC(10, 2) NOT NULL,
+    notes TEXT,
+    payload JSONB,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    PRIMARY KEY (id)
+);
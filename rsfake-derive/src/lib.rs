@@ -0,0 +1,263 @@
+//! `#[derive(FakeSchema)]` for rsfake. Use it through `rsfake::FakeSchema`,
+//! which documents the supported attributes.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use serde_json::{json, Map, Value};
+use syn::{
+    parse_macro_input, Data, DeriveInput, Error, Expr, ExprLit, Field, Fields, GenericArgument, Lit, LitStr,
+    PathArguments, RangeLimits, Type, UnOp,
+};
+
+#[proc_macro_derive(FakeSchema, attributes(rsfake))]
+pub fn derive_fake_schema(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input).unwrap_or_else(Error::into_compile_error).into()
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new_spanned(&input.ident, "FakeSchema can only be derived for structs"));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(Error::new_spanned(&input.ident, "FakeSchema requires a struct with named fields"));
+    };
+
+    let mut columns = Vec::new();
+    for field in &fields.named {
+        if let Some(column) = column(field)? {
+            columns.push(column);
+        }
+    }
+    let schema = json!({ "columns": columns }).to_string();
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::rsfake::FakeSchema for #name #ty_generics #where_clause {
+            const SCHEMA: &'static str = #schema;
+        }
+    })
+}
+
+/// Settings for one column, from `#[rsfake(...)]`, `#[dummy(faker = "...")]`
+/// and the field's Rust type, in that order of precedence.
+#[derive(Default)]
+struct Column {
+    name: Option<String>,
+    type_name: Option<String>,
+    args: Map<String, Value>,
+    null_ratio: Option<f64>,
+    as_string: bool,
+    skip: bool,
+}
+
+fn column(field: &Field) -> syn::Result<Option<Value>> {
+    let mut column = Column::default();
+    for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("rsfake")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("type") {
+                column.type_name = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("name") {
+                column.name = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("null_ratio") {
+                let expr: Expr = meta.value()?.parse()?;
+                let ratio = literal(&expr)
+                    .and_then(|v| v.as_f64())
+                    .filter(|r| (0.0..=1.0).contains(r))
+                    .ok_or_else(|| Error::new_spanned(&expr, "null_ratio must be a number between 0 and 1"))?;
+                column.null_ratio = Some(ratio);
+            } else if meta.path.is_ident("range") {
+                let mut range = Map::new();
+                meta.parse_nested_meta(|bound| {
+                    let key = if bound.path.is_ident("start") {
+                        "start"
+                    } else if bound.path.is_ident("end") {
+                        "end"
+                    } else {
+                        return Err(bound.error("expected `start` or `end`"));
+                    };
+                    let expr: Expr = bound.value()?.parse()?;
+                    let value = literal(&expr).ok_or_else(|| Error::new_spanned(&expr, "expected a literal"))?;
+                    range.insert(key.to_string(), value);
+                    Ok(())
+                })?;
+                column.args.insert("range".to_string(), Value::Object(range));
+            } else if meta.path.is_ident("args") {
+                let lit: LitStr = meta.value()?.parse()?;
+                match serde_json::from_str(&lit.value()) {
+                    Ok(Value::Object(args)) => column.args.extend(args),
+                    _ => return Err(Error::new(lit.span(), "args must be a JSON object")),
+                }
+            } else if meta.path.is_ident("as_string") {
+                column.as_string = true;
+            } else if meta.path.is_ident("skip") {
+                column.skip = true;
+            } else {
+                return Err(meta.error("unsupported rsfake attribute"));
+            }
+            Ok(())
+        })?;
+    }
+    if column.skip {
+        return Ok(None);
+    }
+
+    if let Some((type_name, args)) = dummy_faker(field) {
+        if column.type_name.is_none() {
+            column.type_name = type_name;
+        }
+        for (key, value) in args {
+            column.args.entry(key).or_insert(value);
+        }
+    }
+    let type_name = column
+        .type_name
+        .or_else(|| rust_type(&field.ty).map(str::to_string))
+        .ok_or_else(|| {
+            Error::new_spanned(
+                field,
+                "can't infer an rsfake type for this field; add #[rsfake(type = \"...\")]",
+            )
+        })?;
+    if type_name == "Boolean" {
+        column.args.entry("ratio").or_insert(json!(50));
+    }
+
+    let ident = field.ident.as_ref().expect("named fields have identifiers").to_string();
+    let name = column.name.unwrap_or_else(|| ident.trim_start_matches("r#").to_string());
+    let mut def = Map::new();
+    def.insert("name".to_string(), json!(name));
+    def.insert("type".to_string(), json!(type_name));
+    if !column.args.is_empty() {
+        def.insert("args".to_string(), Value::Object(column.args));
+    }
+    if let Some(ratio) = column.null_ratio {
+        def.insert("null_ratio".to_string(), json!(ratio));
+    }
+    if column.as_string {
+        def.insert("as_string".to_string(), json!(true));
+    }
+    Ok(Some(Value::Object(def)))
+}
+
+/// Reads the type and args from a `fake` field attribute such as
+/// `#[dummy(faker = "SafeEmail()")]` or `#[dummy(faker = "18..80")]`. Other
+/// `dummy` forms are left to `#[rsfake(...)]` or the Rust type.
+fn dummy_faker(field: &Field) -> Option<(Option<String>, Map<String, Value>)> {
+    let mut faker = None;
+    for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("dummy")) {
+        let _ = attr.parse_nested_meta(|meta| {
+            let value = meta.value()?;
+            if meta.path.is_ident("faker") {
+                faker = Some(value.parse::<LitStr>()?.value());
+            } else {
+                value.parse::<Expr>()?;
+            }
+            Ok(())
+        });
+    }
+    let expr = syn::parse_str::<Expr>(&faker?).ok()?;
+
+    let mut args = Map::new();
+    match expr {
+        // fake's ranges exclude the end unless written `..=`; rsfake's include it.
+        Expr::Range(range) => {
+            args.insert("range".to_string(), range_args(&range, true)?);
+            Some((None, args))
+        }
+        Expr::Call(call) => {
+            let Expr::Path(func) = call.func.as_ref() else {
+                return None;
+            };
+            let type_name = func.path.segments.last()?.ident.to_string();
+            for arg in &call.args {
+                match arg {
+                    Expr::Range(range) => {
+                        args.insert("range".to_string(), range_args(range, false)?);
+                    }
+                    arg if type_name == "Boolean" => {
+                        if let Some(ratio) = literal(arg) {
+                            args.insert("ratio".to_string(), ratio);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Some((Some(type_name), args))
+        }
+        Expr::Path(path) => Some((Some(path.path.segments.last()?.ident.to_string()), args)),
+        _ => None,
+    }
+}
+
+/// A `{ "start", "end" }` object from a literal range. With `inclusive_end`,
+/// the end of a half-open integer range is moved in by one.
+fn range_args(range: &syn::ExprRange, inclusive_end: bool) -> Option<Value> {
+    let start = literal(range.start.as_deref()?)?;
+    let mut end = literal(range.end.as_deref()?)?;
+    if inclusive_end && matches!(range.limits, RangeLimits::HalfOpen(_)) {
+        if let Some(n) = end.as_i64() {
+            end = json!(n - 1);
+        }
+    }
+    Some(json!({ "start": start, "end": end }))
+}
+
+/// The JSON value of a numeric, string or boolean literal, possibly negated.
+fn literal(expr: &Expr) -> Option<Value> {
+    match expr {
+        Expr::Lit(ExprLit { lit, .. }) => match lit {
+            Lit::Int(i) => i
+                .base10_parse::<i64>()
+                .map(Value::from)
+                .or_else(|_| i.base10_parse::<u64>().map(Value::from))
+                .ok(),
+            Lit::Float(f) => f.base10_parse::<f64>().ok().map(Value::from),
+            Lit::Str(s) => Some(json!(s.value())),
+            Lit::Bool(b) => Some(json!(b.value)),
+            _ => None,
+        },
+        Expr::Unary(unary) if matches!(unary.op, UnOp::Neg(_)) => match literal(&unary.expr)? {
+            Value::Number(n) if n.is_i64() => Some(json!(-n.as_i64()?)),
+            Value::Number(n) => Some(json!(-n.as_f64()?)),
+            _ => None,
+        },
+        Expr::Paren(paren) => literal(&paren.expr),
+        _ => None,
+    }
+}
+
+/// The rsfake type for fields whose Rust type has an obvious generator,
+/// looking through `Option`.
+fn rust_type(ty: &Type) -> Option<&'static str> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident == "Option" {
+        let PathArguments::AngleBracketed(generics) = &segment.arguments else {
+            return None;
+        };
+        return match generics.args.first()? {
+            GenericArgument::Type(inner) => rust_type(inner),
+            _ => None,
+        };
+    }
+    Some(match segment.ident.to_string().as_str() {
+        "u8" => "u8",
+        "u16" => "u16",
+        "u32" => "u32",
+        "u64" => "u64",
+        "i8" => "i8",
+        "i16" => "i16",
+        "i32" => "i32",
+        "i64" => "i64",
+        "f32" => "f32",
+        "f64" => "f64",
+        "bool" => "Boolean",
+        "String" => "Word",
+        _ => return None,
+    })
+}
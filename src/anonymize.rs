@@ -0,0 +1,179 @@
+use std::collections::HashMap;
+
+use hmac::{Hmac, Mac};
+use polars::prelude::*;
+use serde_json::Value;
+use sha2::Sha256;
+use thiserror::Error;
+
+use crate::generate::{create_series_from_type, GenerateError, RowSeeds};
+use crate::mask::{mask_series, MaskError};
+use crate::schema::Schema;
+
+#[derive(Error, Debug)]
+pub enum AnonymizeError {
+    #[error("Polars error: {0}")]
+    Polars(#[from] polars::error::PolarsError),
+    #[error("Generate error: {0}")]
+    Generate(#[from] GenerateError),
+    #[error("Mask error: {0}")]
+    Mask(#[from] MaskError),
+    #[error("Invalid mapping: {0}")]
+    InvalidMapping(String),
+    #[error("Column '{0}' not found in input")]
+    MissingColumn(String),
+}
+
+/// Replaces each column listed in `schema` with fakes of its configured
+/// `type`, or transforms it in place with its `mask`.
+///
+/// For `type` columns, every distinct input value is keyed with HMAC-SHA256
+/// under `key` and the digest seeds the generator, so the same value always
+/// maps to the same fake across files and runs. Nulls and row order are kept.
+///
+/// ```
+/// use polars::prelude::*;
+/// use rsfake::anonymize::anonymize_dataframe;
+/// use rsfake::Schema;
+///
+/// let df = df!("name" => [Some("Ada"), None, Some("Ada")])?;
+/// let schema = Schema::from_json(r#"{ "columns": [{ "name": "name", "type": "Name" }] }"#)?;
+/// let masked = anonymize_dataframe(df, &schema, b"secret")?;
+/// let names = masked.column("name")?.str()?;
+/// assert_eq!(names.get(0), names.get(2));
+/// assert_ne!(names.get(0), Some("Ada"));
+/// assert_eq!(names.get(1), None);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn anonymize_dataframe(mut df: DataFrame, schema: &Schema, key: &[u8]) -> Result<DataFrame, AnonymizeError> {
+    // Masks look up other columns, e.g. `date_shift` entities, in the original data.
+    let original = df.clone();
+    for col_def in schema.columns() {
+        let col_name = col_def
+            .get("name")
+            .and_then(|n| n.as_str())
+            .ok_or_else(|| AnonymizeError::InvalidMapping("every column needs a 'name'".to_string()))?;
+        let series = original
+            .column(col_name)
+            .map_err(|_| AnonymizeError::MissingColumn(col_name.to_string()))?;
+
+        let replaced = match (col_def.get("mask").and_then(|m| m.as_str()), col_def.get("type").and_then(|t| t.as_str())) {
+            (Some(mask), _) => mask_series(&original, series, mask, col_def, key)?,
+            (None, Some(col_type)) => pseudonymize(series, col_type, col_def, key)?,
+            (None, None) => {
+                return Err(AnonymizeError::InvalidMapping(format!("column '{}' needs a 'type' or a 'mask'", col_name)))
+            }
+        };
+        df.replace(col_name, replaced)?;
+    }
+    Ok(df)
+}
+
+fn pseudonymize(series: &Series, col_type: &str, col_def: &Value, key: &[u8]) -> Result<Series, AnonymizeError> {
+    let values = series.cast(&DataType::String)?;
+    let values = values.str()?;
+
+    // Generate one fake per distinct value, then gather them back into row order.
+    let mut positions: HashMap<&str, IdxSize> = HashMap::new();
+    let mut seeds: Vec<[u8; 32]> = Vec::new();
+    let indices: IdxCa = values
+        .into_iter()
+        .map(|value| {
+            value.map(|value| {
+                *positions.entry(value).or_insert_with(|| {
+                    seeds.push(keyed_digest(key, value));
+                    (seeds.len() - 1) as IdxSize
+                })
+            })
+        })
+        .collect();
+
+    let fakes = create_series_from_type(col_type, series.name(), &RowSeeds::Keyed(&seeds), col_def)?;
+    Ok(fakes.take(&indices)?)
+}
+
+/// HMAC-SHA256 of `value` under `key`.
+pub fn keyed_digest(key: &[u8], value: &str) -> [u8; 32] {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(value.as_bytes());
+    mac.finalize().into_bytes().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn anonymize(df: DataFrame, schema: &str, key: &[u8]) -> Result<DataFrame, AnonymizeError> {
+        anonymize_dataframe(df, &Schema::from_json(schema).unwrap(), key)
+    }
+
+    fn strings(df: &DataFrame, column: &str) -> Vec<Option<String>> {
+        df.column(column).unwrap().str().unwrap().into_iter().map(|v| v.map(str::to_string)).collect()
+    }
+
+    const NAMES: &str = r#"{ "columns": [{ "name": "name", "type": "Name" }] }"#;
+
+    #[test]
+    fn same_value_and_key_give_the_same_fake_across_frames() {
+        let first = anonymize(df!("name" => ["Ada", "Alan"]).unwrap(), NAMES, b"k").unwrap();
+        let second = anonymize(df!("name" => ["Grace", "Alan", "Ada"]).unwrap(), NAMES, b"k").unwrap();
+        let (first, second) = (strings(&first, "name"), strings(&second, "name"));
+        assert_eq!(first[0], second[2]);
+        assert_eq!(first[1], second[1]);
+        assert_ne!(first[0], first[1]);
+    }
+
+    #[test]
+    fn different_keys_give_different_fakes() {
+        let df = df!("name" => ["Ada", "Alan", "Grace"]).unwrap();
+        let a = anonymize(df.clone(), NAMES, b"one").unwrap();
+        let b = anonymize(df, NAMES, b"two").unwrap();
+        assert_ne!(strings(&a, "name"), strings(&b, "name"));
+    }
+
+    #[test]
+    fn unlisted_columns_and_order_are_kept() {
+        let df = df!("id" => [1i32, 2, 3], "name" => [Some("Ada"), None, Some("Ada")]).unwrap();
+        let masked = anonymize(df.clone(), NAMES, b"k").unwrap();
+        assert_eq!(masked.get_column_names(), ["id", "name"]);
+        assert!(masked.column("id").unwrap().equals(df.column("id").unwrap()));
+        let names = strings(&masked, "name");
+        assert_eq!(names[1], None);
+        assert_eq!(names[0], names[2]);
+    }
+
+    #[test]
+    fn non_string_columns_are_keyed_by_their_text() {
+        let df = df!("code" => [7i64, 8, 7]).unwrap();
+        let masked = anonymize(df, r#"{ "columns": [{ "name": "code", "type": "u32" }] }"#, b"k").unwrap();
+        let codes: Vec<Option<u32>> = masked.column("code").unwrap().u32().unwrap().into_iter().collect();
+        assert_eq!(codes[0], codes[2]);
+    }
+
+    #[test]
+    fn masks_run_in_place() {
+        let df = df!("card" => ["4111-1111"]).unwrap();
+        let schema = r#"{ "columns": [{ "name": "card", "mask": "hash", "args": { "length": 8 } }] }"#;
+        let masked = anonymize(df, schema, b"k").unwrap();
+        assert_eq!(strings(&masked, "card")[0].as_ref().unwrap().len(), 8);
+    }
+
+    #[test]
+    fn mapping_errors() {
+        let df = df!("name" => ["Ada"]).unwrap();
+        let missing = anonymize(df.clone(), r#"{ "columns": [{ "name": "email", "type": "SafeEmail" }] }"#, b"k");
+        assert!(matches!(missing, Err(AnonymizeError::MissingColumn(c)) if c == "email"));
+        let untyped = anonymize(df.clone(), r#"{ "columns": [{ "name": "name" }] }"#, b"k");
+        assert!(matches!(untyped, Err(AnonymizeError::InvalidMapping(_))));
+        let unknown = anonymize(df, r#"{ "columns": [{ "name": "name", "mask": "scramble" }] }"#, b"k");
+        assert!(matches!(unknown, Err(AnonymizeError::Mask(MaskError::UnsupportedMask(_)))));
+    }
+
+    #[test]
+    fn keyed_digest_depends_on_key_and_value() {
+        assert_eq!(keyed_digest(b"k", "a"), keyed_digest(b"k", "a"));
+        assert_ne!(keyed_digest(b"k", "a"), keyed_digest(b"k", "b"));
+        assert_ne!(keyed_digest(b"k", "a"), keyed_digest(b"j", "a"));
+        assert_ne!(keyed_digest(b"", "a"), [0; 32]);
+    }
+}
@@ -0,0 +1,200 @@
+use serde_json::{json, Value};
+
+/// A generator picked for a column from its name by
+/// [`crate::Schema::resolve_auto_types`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct AutoType {
+    /// The column's name, with its parents' for nested columns (`customer.email`).
+    pub column: String,
+    pub type_name: &'static str,
+    /// Args added with the type; a column's own `args` are kept instead.
+    pub args: Option<Value>,
+    /// False when nothing in the name matched and the column fell back to `Word`.
+    pub matched: bool,
+}
+
+/// Picks a generator from a column name, or `None` when nothing matches.
+/// Names are compared case-insensitively by their words, so `firstName`,
+/// `first_name` and `First Name` are the same.
+///
+/// ```
+/// use rsfake::auto::type_from_name;
+///
+/// assert_eq!(type_from_name("email").unwrap().0, "SafeEmail");
+/// assert_eq!(type_from_name("billingZipCode").unwrap().0, "ZipCode");
+/// assert_eq!(type_from_name("created_at").unwrap().0, "DateTime");
+/// assert_eq!(type_from_name("customer_id").unwrap().0, "Sequence");
+/// assert!(type_from_name("frobnicator").is_none());
+/// ```
+pub fn type_from_name(name: &str) -> Option<(&'static str, Option<Value>)> {
+    let words = words(name);
+    let (first, last) = (words.first()?.as_str(), words.last()?.as_str());
+
+    if let Some(found) = by_name(&words.join("_")) {
+        return Some(found);
+    }
+    match (first, last) {
+        ("is" | "has" | "can" | "should", _) if words.len() > 1 => return Some(("Boolean", Some(json!({ "ratio": 50 })))),
+        (_, "id") => return Some(("Sequence", None)),
+        (_, "at" | "time" | "timestamp") => return Some(("DateTime", None)),
+        (_, "date") => return Some(("Date", None)),
+        // `created_on`, `born_on`, but not `add_on` or `turn_on`.
+        (_, "on") if words.len() > 1 && is_participle(&words[words.len() - 2]) => return Some(("Date", None)),
+        _ => {}
+    }
+    // `billing_zip_code`, `home_phone`: the last words often carry the meaning.
+    if words.len() > 2 {
+        if let Some(found) = by_name(&words[words.len() - 2..].join("_")) {
+            return Some(found);
+        }
+    }
+    match last {
+        // Alone, these say too little about the rest of the name (`product_name`, `status_code`).
+        "name" | "code" | "type" | "number" => None,
+        _ if words.len() > 1 => by_name(last),
+        _ => None,
+    }
+}
+
+/// Known column names, written as lowercase words joined by `_`.
+fn by_name(name: &str) -> Option<(&'static str, Option<Value>)> {
+    let range = |start: Value, end: Value| Some(json!({ "range": { "start": start, "end": end } }));
+    Some(match name {
+        "id" => ("Sequence", None),
+        "email" | "email_address" | "mail" => ("SafeEmail", None),
+        "first_name" | "firstname" | "given_name" | "forename" | "fname" => ("FirstName", None),
+        "last_name" | "lastname" | "surname" | "family_name" | "lname" => ("LastName", None),
+        "name" | "full_name" | "fullname" | "display_name" | "contact_name" | "customer_name" => ("Name", None),
+        "username" | "user_name" | "login" | "handle" => ("Username", None),
+        "password" => ("Password", None),
+        "phone" | "phone_number" | "telephone" | "tel" => ("PhoneNumber", None),
+        "mobile" | "mobile_number" | "cell" | "cell_phone" => ("CellNumber", None),
+        "zip" | "zipcode" | "zip_code" | "postcode" | "post_code" | "postal_code" => ("ZipCode", None),
+        "street" | "street_name" | "address" | "street_address" => ("StreetName", None),
+        "city" | "town" => ("CityName", None),
+        "state" | "province" => ("StateName", None),
+        "country" => ("CountryName", None),
+        "country_code" => ("CountryCode", None),
+        "latitude" | "lat" => ("Latitude", None),
+        "longitude" | "lng" | "lon" => ("Longitude", None),
+        "timezone" | "time_zone" | "tz" => ("TimeZone", None),
+        "ip" | "ip_address" | "ipv4" => ("IPv4", None),
+        "ipv6" => ("IPv6", None),
+        "mac" | "mac_address" => ("MACAddress", None),
+        "user_agent" => ("UserAgent", None),
+        "url" | "uri" | "website" | "homepage" | "link" => ("Url", None),
+        "uuid" | "guid" => ("UUIDv4", None),
+        "company" | "company_name" | "employer" | "organization" | "organisation" => ("CompanyName", None),
+        "industry" => ("Industry", None),
+        "job_title" => ("JobTitle", None),
+        "profession" | "occupation" => ("Profession", None),
+        "description" | "bio" | "about" | "summary" => ("Paragraph", None),
+        "title" | "subject" | "comment" | "note" | "notes" | "message" => ("Sentence", None),
+        "color" | "colour" => ("Color", None),
+        "currency" | "currency_code" => ("CurrencyCode", None),
+        "credit_card" | "card_number" | "credit_card_number" => ("CreditCardNumber", None),
+        "licence_plate" | "license_plate" => ("LicencePlate", None),
+        "isbn" => ("Isbn", None),
+        "file_name" | "filename" => ("FileName", None),
+        "file_path" | "path" => ("FilePath", None),
+        "age" => ("u32", range(json!(18), json!(90))),
+        "quantity" | "qty" | "count" => ("u32", range(json!(1), json!(100))),
+        "price" | "amount" | "total" | "cost" | "balance" => ("f64", range(json!(0.0), json!(1000.0))),
+        "timestamp" | "created" | "updated" | "modified" => ("DateTime", None),
+        "date" | "birthday" | "birth_date" | "birthdate" | "dob" | "date_of_birth" => ("Date", None),
+        "time" => ("Time", None),
+        "active" | "enabled" | "verified" | "deleted" => ("Boolean", Some(json!({ "ratio": 50 }))),
+        _ => return None,
+    })
+}
+
+/// Whether `word` reads like a past participle, as in `shipped_on`.
+fn is_participle(word: &str) -> bool {
+    (word.ends_with("ed") && word.len() > 3) || matches!(word, "born" | "done" | "due" | "paid" | "sent" | "made")
+}
+
+/// The lowercase words of a name, split at non-alphanumerics and camelCase humps.
+fn words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut prev_lower = false;
+    for c in name.chars() {
+        if (!c.is_alphanumeric() || (c.is_uppercase() && prev_lower)) && !word.is_empty() {
+            words.push(std::mem::take(&mut word));
+        }
+        if c.is_alphanumeric() {
+            word.extend(c.to_lowercase());
+        }
+        prev_lower = c.is_lowercase() || c.is_ascii_digit();
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn type_name(name: &str) -> Option<&'static str> {
+        type_from_name(name).map(|(type_name, _)| type_name)
+    }
+
+    #[test]
+    fn words_split_case_styles() {
+        assert_eq!(words("firstName"), ["first", "name"]);
+        assert_eq!(words("FirstName"), ["first", "name"]);
+        assert_eq!(words("first_name"), ["first", "name"]);
+        assert_eq!(words("First Name"), ["first", "name"]);
+        assert_eq!(words("billing-zip.code"), ["billing", "zip", "code"]);
+        assert_eq!(words("address2Line"), ["address2", "line"]);
+        assert_eq!(words("IPAddress"), ["ipaddress"]);
+        assert_eq!(words("__id__"), ["id"]);
+        assert!(words("__").is_empty());
+    }
+
+    #[test]
+    fn camel_case_names_match_like_snake_case() {
+        for (camel, snake) in [("firstName", "first_name"), ("billingZipCode", "billing_zip_code"), ("createdAt", "created_at")] {
+            assert_eq!(type_from_name(camel), type_from_name(snake));
+            assert!(type_from_name(camel).is_some());
+        }
+    }
+
+    #[test]
+    fn on_suffix_needs_a_participle() {
+        assert_eq!(type_name("created_on"), Some("Date"));
+        assert_eq!(type_name("shippedOn"), Some("Date"));
+        assert_eq!(type_name("born_on"), Some("Date"));
+        assert_eq!(type_name("add_on"), None);
+        assert_eq!(type_name("turn_on"), None);
+        assert_eq!(type_name("on"), None);
+        assert_eq!(type_name("red_on"), None);
+    }
+
+    #[test]
+    fn suffixes_and_prefixes() {
+        assert_eq!(type_name("customer_id"), Some("Sequence"));
+        assert_eq!(type_name("updated_at"), Some("DateTime"));
+        assert_eq!(type_name("start_date"), Some("Date"));
+        assert_eq!(type_name("is_admin"), Some("Boolean"));
+        assert_eq!(type_name("is"), None);
+        assert_eq!(type_name("home_phone_number"), Some("PhoneNumber"));
+        assert_eq!(type_name("work_email"), Some("SafeEmail"));
+    }
+
+    #[test]
+    fn vague_last_words_dont_match() {
+        assert_eq!(type_name("product_name"), None);
+        assert_eq!(type_name("status_code"), None);
+        assert_eq!(type_name("customer_name"), Some("Name"));
+    }
+
+    #[test]
+    fn known_names_carry_args() {
+        let (type_name, args) = type_from_name("Age").unwrap();
+        assert_eq!(type_name, "u32");
+        assert_eq!(args.unwrap()["range"]["start"], 18);
+    }
+}
@@ -0,0 +1,82 @@
+use std::time::Instant;
+
+use serde::Serialize;
+
+use crate::generate::{GenerateError, Generator};
+use crate::schema::Schema;
+
+#[derive(Debug, Serialize)]
+pub struct BenchReport {
+    pub version: &'static str,
+    pub schema: String,
+    pub threads: usize,
+    pub runs: Vec<BenchRun>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BenchRun {
+    pub rows: usize,
+    pub seconds: f64,
+    pub rows_per_second: f64,
+    /// Peak resident memory during the run, where the platform reports it.
+    pub peak_memory_bytes: Option<u64>,
+}
+
+/// Generates `schema` once for each row count and times it, so results can be
+/// compared across versions.
+pub fn run_benchmark(schema_path: &str, row_counts: &[usize], threads: usize) -> Result<BenchReport, GenerateError> {
+    let schema = Schema::from_path(schema_path)?;
+    let runs = row_counts
+        .iter()
+        .map(|&rows| {
+            reset_peak_memory();
+            let start = Instant::now();
+            let df = Generator::new(schema.clone()).rows(rows).generate()?;
+            let seconds = start.elapsed().as_secs_f64();
+            let peak_memory_bytes = peak_memory_bytes();
+            drop(df);
+            Ok(BenchRun {
+                rows,
+                seconds,
+                rows_per_second: rows as f64 / seconds,
+                peak_memory_bytes,
+            })
+        })
+        .collect::<Result<Vec<BenchRun>, GenerateError>>()?;
+
+    Ok(BenchReport {
+        version: env!("CARGO_PKG_VERSION"),
+        schema: schema_path.to_string(),
+        threads,
+        runs,
+    })
+}
+
+/// Resets the kernel's resident memory high-water mark so each run is measured
+/// on its own rather than against the largest run so far.
+#[cfg(target_os = "linux")]
+fn reset_peak_memory() {
+    let _ = std::fs::write("/proc/self/clear_refs", "5");
+}
+
+#[cfg(not(target_os = "linux"))]
+fn reset_peak_memory() {}
+
+#[cfg(target_os = "linux")]
+fn peak_memory_bytes() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let kilobytes = status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmHWM:"))?
+        .trim()
+        .trim_end_matches("kB")
+        .trim()
+        .parse::<u64>()
+        .ok()?;
+    Some(kilobytes * 1024)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn peak_memory_bytes() -> Option<u64> {
+    None
+}
@@ -0,0 +1,568 @@
+use fake::Fake;
+use fake::faker::company::raw::{Profession, Industry};
+#[cfg(feature="bigdecimal")]
+use fake::bigdecimal::{BigDecimal, PositiveBigDecimal, NegativeBigDecimal, NoBigDecimalPoints};
+use polars::prelude::{Int128Chunked, IntoSeries, NamedFrom, Series};
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::Rng;
+use serde_json::Value;
+use std::fmt::Write as _;
+use std::str::FromStr;
+
+use crate::generate::{GenerateError, RowRng};
+use crate::registry::{string_generator, value_generator, ColumnGenerator, Registry};
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveTime, Timelike, Utc};
+#[cfg(feature = "chrono")]
+use polars::prelude::{DataType, TimeUnit};
+
+/// The dtype of `DateTime` columns: UTC milliseconds, without a time zone.
+#[cfg(feature = "chrono")]
+const DATETIME: DataType = DataType::Datetime(TimeUnit::Milliseconds, None);
+
+/// `Datelike::num_days_from_ce` of 1970-01-01, where polars dates count from.
+#[cfg(feature = "chrono")]
+const UNIX_EPOCH_DAYS_FROM_CE: i32 = 719_163;
+
+/// Registers every built-in type.
+pub(crate) fn register(registry: &mut Registry) {
+    use fake::faker::*;
+    use fake::locales::EN;
+
+    // fake builds these values as a `String`, so they still allocate once per row.
+    macro_rules! faker {
+        ($name:literal, $faker:expr) => {
+            registry.register($name, |_| {
+                Ok(string_generator(|rng, buf| buf.push_str(&$faker.fake_with_rng::<String, _>(rng))))
+            });
+        };
+    }
+
+    // For fakers that pick from static data, copying the borrowed value avoids an allocation per row.
+    macro_rules! str_faker {
+        ($name:literal, $faker:expr) => {
+            registry.register($name, |_| {
+                Ok(string_generator(|rng, buf| buf.push_str($faker.fake_with_rng::<&str, _>(rng))))
+            });
+        };
+    }
+
+    // Generators with a natural non-string dtype; `"as_string": true` keeps the faker's text form.
+    macro_rules! native {
+        ($name:literal, $faker:expr, $type:ty, $native:expr) => {
+            registry.register($name, |col_def| {
+                Ok(if as_string(col_def) {
+                    string_generator(|rng, buf| buf.push_str(&$faker.fake_with_rng::<String, _>(rng)))
+                } else {
+                    value_generator::<$type, _>($native)
+                })
+            });
+        };
+    }
+
+    // Dates and times are drawn as their physical integers and then given the temporal dtype.
+    #[cfg(feature = "chrono")]
+    macro_rules! temporal {
+        ($name:literal, $faker:expr, $dtype:expr, $native:expr) => {
+            registry.register($name, |col_def| {
+                Ok(if as_string(col_def) {
+                    string_generator(|rng, buf| buf.push_str(&$faker.fake_with_rng::<String, _>(rng)))
+                } else {
+                    Box::new(Temporal(value_generator($native), $dtype))
+                })
+            });
+        };
+    }
+
+    macro_rules! range {
+        ($name:literal, $type:ty, $default_start:expr, $default_end:expr) => {
+            registry.register($name, |col_def| {
+                let (start, end) = get_range_args::<$type>(col_def, $default_start, $default_end)?;
+                Ok(value_generator(move |rng| rng.gen_range(start..=end)))
+            });
+        };
+    }
+
+    // Values written through `Display`.
+    macro_rules! display {
+        ($name:literal, $faker:expr, $type:ty) => {
+            registry.register($name, |_| {
+                Ok(string_generator(|rng, buf| {
+                    write!(buf, "{}", $faker.fake_with_rng::<$type, _>(rng)).expect("writing to a String never fails")
+                }))
+            });
+        };
+    }
+
+    #[cfg(feature = "rust_decimal")]
+    macro_rules! rust_decimal {
+        ($name:literal, $negative:expr, $max_scale:expr) => {
+            registry.register($name, |_| {
+                Ok(string_generator(|rng, buf| {
+                    write!(buf, "{}", fake_rust_decimal(rng, $negative, $max_scale)).expect("writing to a String never fails")
+                }))
+            });
+        };
+    }
+
+    range!("u8", u8, u8::MIN, u8::MAX);
+    range!("u16", u16, u16::MIN, u16::MAX);
+    range!("u32", u32, u32::MIN, u32::MAX);
+    range!("u64", u64, u64::MIN, u64::MAX);
+    range!("i8", i8, i8::MIN, i8::MAX);
+    range!("i16", i16, i16::MIN, i16::MAX);
+    range!("i32", i32, i32::MIN, i32::MAX);
+    range!("i64", i64, i64::MIN, i64::MAX);
+    range!("f32", f32, 0.0, 1.0);
+    range!("f64", f64, 0.0, 1.0);
+    registry.register("decimal128", |col_def| {
+        let args = col_def.get("args");
+        let arg = |key, default| {
+            args.and_then(|a| a.get(key)).map_or(Ok(default), |v| {
+                v.as_u64().map(|v| v as u32).ok_or_else(|| {
+                    GenerateError::InvalidArgument(format!("'{}' must be a non-negative integer", key))
+                })
+            })
+        };
+        let (precision, scale) = (arg("precision", 10)?, arg("scale", 2)?);
+        if !(1..=38).contains(&precision) || scale > precision {
+            return Err(GenerateError::InvalidArgument(
+                "'precision' must be 1 to 38 and 'scale' at most 'precision'".to_string(),
+            ));
+        }
+        // Values are drawn as integers in units of the scale, e.g. cents for a scale of 2.
+        let max = 10i128.pow(precision) - 1;
+        let unit = 10f64.powi(scale as i32);
+        let (start, end) = get_range_args::<f64>(col_def, f64::MIN, f64::MAX)?;
+        let to_units = |v: f64| ((v * unit).round() as i128).clamp(-max, max);
+        Ok(Box::new(DecimalGenerator { start: to_units(start), end: to_units(end), precision, scale }))
+    });
+    registry.register("Boolean", |col_def| {
+        let ratio = get_args_u8(col_def, "ratio")?;
+        Ok(value_generator::<bool, _>(move |rng| boolean::raw::Boolean(EN, ratio).fake_with_rng(rng)))
+    });
+    registry.register("Choice", |col_def| {
+        let (values, weights) = get_args_choice(col_def)?;
+        let dist = WeightedIndex::new(&weights)
+            .map_err(|e| GenerateError::InvalidArgument(format!("Invalid 'weights' argument: {}", e)))?;
+        // Values that are all integers, all numbers or all booleans keep that dtype.
+        Ok(if values.iter().all(Value::is_i64) {
+            let values: Vec<i64> = values.iter().filter_map(Value::as_i64).collect();
+            value_generator(move |rng| values[dist.sample(rng)])
+        } else if values.iter().all(Value::is_number) {
+            let values: Vec<f64> = values.iter().filter_map(Value::as_f64).collect();
+            value_generator(move |rng| values[dist.sample(rng)])
+        } else if values.iter().all(Value::is_boolean) {
+            let values: Vec<bool> = values.iter().filter_map(Value::as_bool).collect();
+            value_generator(move |rng| values[dist.sample(rng)])
+        } else {
+            let values: Vec<String> = values
+                .iter()
+                .map(|v| match v {
+                    Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                })
+                .collect();
+            string_generator(move |rng, buf| buf.push_str(&values[dist.sample(rng)]))
+        })
+    });
+    registry.register("Regex", |col_def| {
+        let pattern = get_args_string(col_def, "pattern")?;
+        let regex = rand_regex::Regex::compile(&ascii_pattern(&pattern), 8)
+            .map_err(|e| GenerateError::InvalidArgument(format!("Invalid 'pattern' argument: {}", e)))?;
+        Ok(string_generator(move |rng, buf| buf.push_str(&rng.sample::<String, _>(&regex))))
+    });
+    registry.register("Sequence", |col_def| {
+        let args = col_def.get("args");
+        let arg = |key| {
+            args.and_then(|a| a.get(key)).map_or(Ok(1), |v| {
+                v.as_i64().ok_or_else(|| GenerateError::InvalidArgument(format!("'{}' must be an integer", key)))
+            })
+        };
+        Ok(Box::new(Sequence { start: arg("start")?, step: arg("step")? }))
+    });
+    str_faker!("Word", lorem::raw::Word(EN));
+    registry.register("Sentence", |col_def| {
+        let (start, end) = get_range_args(col_def, 3, 10)?;
+        Ok(string_generator(move |rng, buf| {
+            buf.push_str(&lorem::raw::Sentence(EN, start..end).fake_with_rng::<String, _>(rng))
+        }))
+    });
+    registry.register("Paragraph", |col_def| {
+        let (start, end) = get_range_args(col_def, 3, 7)?;
+        Ok(string_generator(move |rng, buf| {
+            buf.push_str(&lorem::raw::Paragraph(EN, start..end).fake_with_rng::<String, _>(rng))
+        }))
+    });
+    str_faker!("FirstName", name::raw::FirstName(EN));
+    str_faker!("LastName", name::raw::LastName(EN));
+    str_faker!("Title", name::raw::Title(EN));
+    str_faker!("Suffix", name::raw::Suffix(EN));
+    faker!("Name", name::raw::Name(EN));
+    faker!("NameWithTitle", name::raw::NameWithTitle(EN));
+    str_faker!("Seniority", job::raw::Seniority(EN));
+    str_faker!("Field", job::raw::Field(EN));
+    str_faker!("Position", job::raw::Position(EN));
+    faker!("JobTitle", job::raw::Title(EN));
+    native!("Digit", number::raw::Digit(EN), u8, |rng| rng.gen_range(0..=9));
+    registry.register("NumberWithFormat", |col_def| {
+        let fmt = get_args_string(col_def, "fmt")?;
+        Ok(string_generator(move |rng, buf| {
+            buf.push_str(&number::raw::NumberWithFormat(EN, &fmt).fake_with_rng::<String, _>(rng))
+        }))
+    });
+    str_faker!("FreeEmailProvider", internet::raw::FreeEmailProvider(EN));
+    str_faker!("DomainSuffix", internet::raw::DomainSuffix(EN));
+    faker!("FreeEmail", internet::raw::FreeEmail(EN));
+    faker!("SafeEmail", internet::raw::SafeEmail(EN));
+    faker!("Username", internet::raw::Username(EN));
+    registry.register("Password", |col_def| {
+        let (start, end) = get_range_args(col_def, 8, 20)?;
+        Ok(string_generator(move |rng, buf| {
+            buf.push_str(&internet::raw::Password(EN, start..end).fake_with_rng::<String, _>(rng))
+        }))
+    });
+    registry.register("Url", |_| {
+        Ok(string_generator(|rng, buf| {
+            let host: &str = lorem::raw::Word(EN).fake_with_rng(rng);
+            let suffix: &str = internet::raw::DomainSuffix(EN).fake_with_rng(rng);
+            let path: &str = lorem::raw::Word(EN).fake_with_rng(rng);
+            write!(buf, "https://{}.{}/{}", host, suffix, path).expect("writing to a String never fails")
+        }))
+    });
+    faker!("IPv4", internet::raw::IPv4(EN));
+    faker!("IPv6", internet::raw::IPv6(EN));
+    faker!("IP", internet::raw::IP(EN));
+    faker!("MACAddress", internet::raw::MACAddress(EN));
+    str_faker!("UserAgent", internet::raw::UserAgent(EN));
+    #[cfg(feature = "http")]
+    native!("RfcStatusCode", http::raw::RfcStatusCode(EN), u16, |rng| {
+        http::raw::RfcStatusCode(EN).fake_with_rng::<::http::StatusCode, _>(rng).as_u16()
+    });
+    #[cfg(feature = "http")]
+    native!("ValidStatusCode", http::raw::ValidStatusCode(EN), u16, |rng| {
+        http::raw::ValidStatusCode(EN).fake_with_rng::<::http::StatusCode, _>(rng).as_u16()
+    });
+    #[cfg(feature = "random_color")]
+    faker!("HexColor", color::raw::HexColor(EN));
+    #[cfg(feature = "random_color")]
+    faker!("RgbColor", color::raw::RgbColor(EN));
+    #[cfg(feature = "random_color")]
+    faker!("RgbaColor", color::raw::RgbaColor(EN));
+    #[cfg(feature = "random_color")]
+    faker!("HslColor", color::raw::HslColor(EN));
+    #[cfg(feature = "random_color")]
+    faker!("HslaColor", color::raw::HslaColor(EN));
+    #[cfg(feature = "random_color")]
+    faker!("Color", color::raw::Color(EN));
+    str_faker!("CompanySuffix", company::raw::CompanySuffix(EN));
+    faker!("CompanyName", company::raw::CompanyName(EN));
+    str_faker!("Buzzword", company::raw::Buzzword(EN));
+    str_faker!("BuzzwordMiddle", company::raw::BuzzwordMiddle(EN));
+    str_faker!("BuzzwordTail", company::raw::BuzzwordTail(EN));
+    faker!("CatchPhrase", company::raw::CatchPhrase(EN));
+    str_faker!("BsVerb", company::raw::BsVerb(EN));
+    str_faker!("BsAdj", company::raw::BsAdj(EN));
+    str_faker!("BsNoun", company::raw::BsNoun(EN));
+    faker!("Bs", company::raw::Bs(EN));
+    str_faker!("Profession", Profession(EN));
+    str_faker!("Industry", Industry(EN));
+    str_faker!("CityPrefix", address::raw::CityPrefix(EN));
+    str_faker!("CitySuffix", address::raw::CitySuffix(EN));
+    faker!("CityName", address::raw::CityName(EN));
+    str_faker!("CountryName", address::raw::CountryName(EN));
+    str_faker!("CountryCode", address::raw::CountryCode(EN));
+    str_faker!("StreetSuffix", address::raw::StreetSuffix(EN));
+    faker!("StreetName", address::raw::StreetName(EN));
+    str_faker!("TimeZone", address::raw::TimeZone(EN));
+    str_faker!("StateName", address::raw::StateName(EN));
+    str_faker!("StateAbbr", address::raw::StateAbbr(EN));
+    str_faker!("SecondaryAddressType", address::raw::SecondaryAddressType(EN));
+    faker!("SecondaryAddress", address::raw::SecondaryAddress(EN));
+    faker!("ZipCode", address::raw::ZipCode(EN));
+    faker!("PostCode", address::raw::PostCode(EN));
+    // A native `u32` with fake's 3 to 5 digits; `as_string` keeps fake's text, which may have leading zeros.
+    native!("BuildingNumber", address::raw::BuildingNumber(EN), u32, |rng| {
+        let digits = rng.gen_range(3..=5);
+        rng.gen_range(10u32.pow(digits - 1)..10u32.pow(digits))
+    });
+    native!("Latitude", address::raw::Latitude(EN), f64, |rng| address::raw::Latitude(EN).fake_with_rng(rng));
+    native!("Longitude", address::raw::Longitude(EN), f64, |rng| address::raw::Longitude(EN).fake_with_rng(rng));
+    registry.register("Geohash", |col_def| {
+        let precision = get_args_u8(col_def, "precision")?;
+        Ok(string_generator(move |rng, buf| {
+            buf.push_str(&address::raw::Geohash(EN, precision).fake_with_rng::<String, _>(rng))
+        }))
+    });
+    faker!("LicencePlate", automotive::raw::LicencePlate(fake::locales::FR_FR));
+    faker!("Isbn", barcode::raw::Isbn(EN));
+    faker!("Isbn13", barcode::raw::Isbn13(EN));
+    faker!("Isbn10", barcode::raw::Isbn10(EN));
+    faker!("PhoneNumber", phone_number::raw::PhoneNumber(EN));
+    faker!("CellNumber", phone_number::raw::CellNumber(EN));
+    #[cfg(feature = "chrono")]
+    temporal!("Time", chrono::raw::Time(EN), DataType::Time, |rng| {
+        let time: NaiveTime = chrono::raw::Time(EN).fake_with_rng(rng);
+        i64::from(time.num_seconds_from_midnight()) * 1_000_000_000
+    });
+    #[cfg(feature = "chrono")]
+    temporal!("Date", chrono::raw::Date(EN), DataType::Date, |rng| {
+        let date: NaiveDate = chrono::raw::Date(EN).fake_with_rng(rng);
+        date.num_days_from_ce() - UNIX_EPOCH_DAYS_FROM_CE
+    });
+    #[cfg(feature = "chrono")]
+    temporal!("DateTime", chrono::raw::DateTime(EN), DATETIME, |rng| {
+        chrono::raw::DateTime(EN).fake_with_rng::<DateTime<Utc>, _>(rng).timestamp_millis()
+    });
+    #[cfg(feature = "chrono")]
+    display!("Duration", chrono::raw::Duration(EN), Duration);
+    #[cfg(feature = "chrono")]
+    registry.register("DateTimeBefore", |col_def| {
+        let dt = get_args_datetime(col_def, "dt")?;
+        Ok(datetime_generator(col_def, move |rng| chrono::raw::DateTimeBefore(EN, dt).fake_with_rng(rng)))
+    });
+    #[cfg(feature = "chrono")]
+    registry.register("DateTimeAfter", |col_def| {
+        let dt = get_args_datetime(col_def, "dt")?;
+        Ok(datetime_generator(col_def, move |rng| chrono::raw::DateTimeAfter(EN, dt).fake_with_rng(rng)))
+    });
+    #[cfg(feature = "chrono")]
+    registry.register("DateTimeBetween", |col_def| {
+        let (start, end) = get_args_datetimerange(col_def)?;
+        Ok(datetime_generator(col_def, move |rng| chrono::raw::DateTimeBetween(EN, start, end).fake_with_rng(rng)))
+    });
+    faker!("FilePath", filesystem::raw::FilePath(EN));
+    faker!("FileName", filesystem::raw::FileName(EN));
+    str_faker!("FileExtension", filesystem::raw::FileExtension(EN));
+    faker!("DirPath", filesystem::raw::DirPath(EN));
+    faker!("Bic", finance::raw::Bic(EN));
+    #[cfg(feature = "uuid")]
+    display!("UUIDv1", fake::uuid::UUIDv1, uuid::Uuid);
+    #[cfg(feature = "uuid")]
+    display!("UUIDv3", fake::uuid::UUIDv3, uuid::Uuid);
+    #[cfg(feature = "uuid")]
+    display!("UUIDv4", fake::uuid::UUIDv4, uuid::Uuid);
+    #[cfg(feature = "uuid")]
+    display!("UUIDv5", fake::uuid::UUIDv5, uuid::Uuid);
+    str_faker!("CurrencyCode", currency::raw::CurrencyCode(EN));
+    str_faker!("CurrencyName", currency::raw::CurrencyName(EN));
+    str_faker!("CurrencySymbol", currency::raw::CurrencySymbol(EN));
+    faker!("CreditCardNumber", creditcard::raw::CreditCardNumber(EN));
+    #[cfg(feature = "rust_decimal")]
+    rust_decimal!("Decimal", None, rust_decimal::Decimal::MAX_SCALE);
+    #[cfg(feature = "rust_decimal")]
+    rust_decimal!("PositiveDecimal", Some(false), rust_decimal::Decimal::MAX_SCALE);
+    #[cfg(feature = "rust_decimal")]
+    rust_decimal!("NegativeDecimal", Some(true), rust_decimal::Decimal::MAX_SCALE);
+    #[cfg(feature = "rust_decimal")]
+    rust_decimal!("NoDecimalPoints", None, 0);
+    #[cfg(feature = "bigdecimal")]
+    display!("BigDecimal", BigDecimal, bigdecimal::BigDecimal);
+    #[cfg(feature = "bigdecimal")]
+    display!("PositiveBigDecimal", PositiveBigDecimal, bigdecimal::BigDecimal);
+    #[cfg(feature = "bigdecimal")]
+    display!("NegativeBigDecimal", NegativeBigDecimal, bigdecimal::BigDecimal);
+    #[cfg(feature = "bigdecimal")]
+    display!("NoBigDecimalPoints", NoBigDecimalPoints, bigdecimal::BigDecimal);
+}
+
+fn as_string(col_def: &Value) -> bool {
+    col_def.get("as_string").and_then(|v| v.as_bool()).unwrap_or(false)
+}
+
+/// fake's rust_decimal fakers draw the scale from the whole `u32` range, which
+/// rust_decimal rejects above 28, so decimals are built here with a valid scale.
+#[cfg(feature = "rust_decimal")]
+fn fake_rust_decimal<R: Rng>(rng: &mut R, negative: Option<bool>, max_scale: u32) -> rust_decimal::Decimal {
+    let negative = negative.unwrap_or_else(|| rng.gen());
+    rust_decimal::Decimal::from_parts(rng.gen(), rng.gen(), rng.gen(), negative, rng.gen_range(0..=max_scale))
+}
+
+/// A generator of physical values (days, milliseconds or nanoseconds) cast
+/// to a temporal dtype.
+#[cfg(feature = "chrono")]
+struct Temporal(Box<dyn ColumnGenerator>, DataType);
+
+#[cfg(feature = "chrono")]
+impl ColumnGenerator for Temporal {
+    fn generate(&self, name: &str, rows: usize, rng: &mut RowRng) -> Result<Series, GenerateError> {
+        Ok(self.0.generate(name, rows, rng)?.cast(&self.1)?)
+    }
+}
+
+/// A `DateTime` column drawn by `f`, or its RFC 3339 text with `"as_string": true`.
+#[cfg(feature = "chrono")]
+fn datetime_generator<F>(col_def: &Value, f: F) -> Box<dyn ColumnGenerator>
+where
+    F: Fn(&mut rand::rngs::StdRng) -> DateTime<Utc> + Send + Sync + 'static,
+{
+    if as_string(col_def) {
+        string_generator(move |rng, buf| buf.push_str(&f(rng).to_rfc3339()))
+    } else {
+        Box::new(Temporal(value_generator(move |rng| f(rng).timestamp_millis()), DATETIME))
+    }
+}
+
+fn get_range_args<T>(col_def: &Value, default_start: T, default_end: T) -> Result<(T, T), GenerateError>
+where
+    T: FromStr + PartialOrd,
+    <T as FromStr>::Err: std::fmt::Debug,
+{
+    let range = col_def.get("args").and_then(|a| a.get("range"));
+
+    let parse_value = |v: &Value| -> Result<T, GenerateError> {
+        match v {
+            Value::String(s) => s.parse().map_err(|_| GenerateError::InvalidArgument("Invalid string range value".to_string())),
+            Value::Number(n) => n.to_string().parse().map_err(|_| GenerateError::InvalidArgument("Invalid numeric range value".to_string())),
+            _ => Err(GenerateError::InvalidArgument("Invalid range value type".to_string())),
+        }
+    };
+
+    let start = range
+        .and_then(|r| r.get("start"))
+        .map(parse_value)
+        .transpose()?
+        .unwrap_or(default_start);
+
+    let end = range
+        .and_then(|r| r.get("end"))
+        .map(parse_value)
+        .transpose()?
+        .unwrap_or(default_end);
+
+    if start <= end {
+        Ok((start, end))
+    } else {
+        Err(GenerateError::InvalidArgument("'start' must be less than or equal to 'end'".to_string()))
+    }
+}
+
+fn get_args_string(col_def: &Value, key: &str) -> Result<String, GenerateError> {
+    col_def
+        .get("args")
+        .and_then(|a| a.get(key))
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+        .ok_or_else(|| GenerateError::InvalidArgument(format!("Missing '{}' argument", key)))
+}
+
+fn get_args_choice(col_def: &Value) -> Result<(Vec<Value>, Vec<f64>), GenerateError> {
+    let args = col_def.get("args");
+    let values = args
+        .and_then(|a| a.get("values"))
+        .and_then(|v| v.as_array())
+        .filter(|v| !v.is_empty())
+        .ok_or_else(|| GenerateError::InvalidArgument("Missing or empty 'values' argument".to_string()))?
+        .clone();
+
+    let weights = match args.and_then(|a| a.get("weights")) {
+        Some(weights) => weights
+            .as_array()
+            .filter(|w| w.len() == values.len())
+            .and_then(|w| w.iter().map(|v| v.as_f64()).collect::<Option<Vec<f64>>>())
+            .ok_or_else(|| GenerateError::InvalidArgument("'weights' must be numbers, one per value".to_string()))?,
+        None => vec![1.0; values.len()],
+    };
+    Ok((values, weights))
+}
+
+/// Fixed-point numbers with `precision` digits, `scale` of them after the point.
+struct DecimalGenerator {
+    start: i128,
+    end: i128,
+    precision: u32,
+    scale: u32,
+}
+
+impl ColumnGenerator for DecimalGenerator {
+    fn generate(&self, name: &str, rows: usize, rng: &mut RowRng) -> Result<Series, GenerateError> {
+        let values: Vec<i128> = (0..rows).map(|_| rng.row().gen_range(self.start..=self.end)).collect();
+        Ok(Int128Chunked::from_vec(name, values)
+            .into_decimal_unchecked(Some(self.precision as usize), self.scale as usize)
+            .into_series())
+    }
+}
+
+/// Row numbers: `start`, `start + step`, ... counting from the first row of
+/// the column, so batches carry on where the previous one stopped.
+struct Sequence {
+    start: i64,
+    step: i64,
+}
+
+impl ColumnGenerator for Sequence {
+    fn generate(&self, name: &str, rows: usize, rng: &mut RowRng) -> Result<Series, GenerateError> {
+        let values: Vec<i64> = (0..rows)
+            .map(|_| {
+                rng.row();
+                self.start.wrapping_add((rng.index() as i64).wrapping_mul(self.step))
+            })
+            .collect();
+        Ok(Series::new(name, values))
+    }
+}
+
+/// Rewrites a pattern for generation: leading `^` and trailing `$` anchors
+/// are dropped, since every generated value is a whole match, and `\d`, `\w`
+/// and `\s` match ASCII only, as in JSON Schema's ECMAScript patterns, with
+/// `\s` generating a space.
+fn ascii_pattern(pattern: &str) -> String {
+    let pattern = pattern.strip_prefix('^').unwrap_or(pattern);
+    let mut out = String::with_capacity(pattern.len());
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            if c == '$' && chars.peek().is_none() {
+                break;
+            }
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('d') => out.push_str("[0-9]"),
+            Some('D') => out.push_str("[^0-9]"),
+            Some('w') => out.push_str("[0-9A-Za-z_]"),
+            Some('W') => out.push_str("[^0-9A-Za-z_]"),
+            Some('s') => out.push(' '),
+            Some('S') => out.push_str("[^ \\t\\n\\r]"),
+            Some(other) => {
+                out.push('\\');
+                out.push(other);
+            }
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+fn get_args_u8(col_def: &Value, key: &str) -> Result<u8, GenerateError> {
+    col_def
+        .get("args")
+        .and_then(|a| a.get(key))
+        .and_then(|v| v.as_u64())
+        .map(|v| v as u8)
+        .ok_or_else(|| GenerateError::InvalidArgument(format!("Invalid '{}' argument", key)))
+}
+
+#[cfg(feature = "chrono")]
+fn get_args_datetime(col_def: &Value, key: &str) -> Result<DateTime<Utc>, GenerateError> {
+    col_def
+        .get("args")
+        .and_then(|a| a.get(key))
+        .and_then(|v| v.as_str())
+        .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+        .map(|dt| dt.with_timezone(&Utc))
+        .ok_or_else(|| GenerateError::InvalidArgument(format!("Invalid '{}' datetime", key)))
+}
+
+#[cfg(feature = "chrono")]
+fn get_args_datetimerange(col_def: &Value) -> Result<(DateTime<Utc>, DateTime<Utc>), GenerateError> {
+    let start = get_args_datetime(col_def, "start")?;
+    let end = get_args_datetime(col_def, "end")?;
+    if start <= end {
+        Ok((start, end))
+    } else {
+        Err(GenerateError::InvalidArgument("Invalid datetime range".to_string()))
+    }
+}
\ No newline at end of file
@@ -0,0 +1,354 @@
+use std::path::{Path, PathBuf};
+
+use serde_json::{Map, Value};
+
+use crate::generate::GenerateError;
+use crate::schema::read_value;
+
+/// Templates that refer to each other deeper than this are assumed to be circular.
+const MAX_DEPTH: usize = 32;
+
+/// Resolves a schema's composition keywords, returning a plain schema:
+///
+/// - a top-level `"$include"` (a path or list of paths) imports the
+///   `definitions` of other schema files, without their columns
+/// - a `{ "$include": "path" }` entry in `columns` is replaced by that file's columns
+/// - `definitions` names column templates; a column with `"$ref": "name"`
+///   starts from the template and overrides it key by key, so `args` can be
+///   changed per use. Templates can `$ref` other templates, and a template
+///   that is a list of columns is spliced in whole.
+///
+/// Include paths are relative to the including file.
+pub(crate) fn resolve(json: Value, base_dir: &Path) -> Result<Value, GenerateError> {
+    let Value::Object(doc) = json else {
+        return Ok(json);
+    };
+    Ok(Value::Object(Resolver { includes: Vec::new() }.document(doc, base_dir)?.0))
+}
+
+/// A resolved document and the definitions it makes available.
+type Resolved = (Map<String, Value>, Map<String, Value>);
+
+struct Resolver {
+    /// Files being included, to detect include cycles.
+    includes: Vec<PathBuf>,
+}
+
+impl Resolver {
+    /// Resolves a document's columns, returning it together with its
+    /// definitions, which include files pass on to whoever includes them.
+    fn document(&mut self, mut doc: Map<String, Value>, dir: &Path) -> Result<Resolved, GenerateError> {
+        let mut definitions = match doc.shift_remove("definitions") {
+            Some(Value::Object(definitions)) => definitions,
+            None => Map::new(),
+            Some(_) => return Err(invalid("'definitions' must be an object of column templates")),
+        };
+        let includes = match doc.shift_remove("$include") {
+            Some(Value::String(path)) => vec![path],
+            Some(Value::Array(paths)) => paths
+                .iter()
+                .map(|path| path.as_str().map(str::to_string))
+                .collect::<Option<Vec<String>>>()
+                .ok_or_else(|| invalid("'$include' must be a path or a list of paths"))?,
+            None => Vec::new(),
+            Some(_) => return Err(invalid("'$include' must be a path or a list of paths")),
+        };
+        // The including file's own definitions win, then earlier includes over later ones.
+        for path in includes {
+            let (_, included) = self.include(&path, dir)?;
+            for (name, template) in included {
+                definitions.entry(name).or_insert(template);
+            }
+        }
+
+        if let Some(Value::Array(columns)) = doc.get_mut("columns") {
+            *columns = self.columns(std::mem::take(columns), &definitions, dir, 0)?;
+        }
+        Ok((doc, definitions))
+    }
+
+    /// Reads and resolves the schema file at `path`; a file holding just a
+    /// list is taken as its columns.
+    fn include(&mut self, path: &str, dir: &Path) -> Result<Resolved, GenerateError> {
+        let file = dir.join(path);
+        let canonical = file
+            .canonicalize()
+            .map_err(|e| invalid(format!("Can't include '{}': {}", file.display(), e)))?;
+        if self.includes.contains(&canonical) {
+            return Err(invalid(format!("'{}' is included in a cycle", file.display())));
+        }
+        let doc = match read_value(&file).map_err(|e| invalid(format!("Can't include '{}': {}", file.display(), e)))? {
+            Value::Object(doc) => doc,
+            Value::Array(columns) => Map::from_iter([("columns".to_string(), Value::Array(columns))]),
+            _ => return Err(invalid(format!("'{}' isn't a schema or a list of columns", file.display()))),
+        };
+
+        self.includes.push(canonical);
+        let resolved = self.document(doc, file.parent().unwrap_or(Path::new("")));
+        self.includes.pop();
+        resolved
+    }
+
+    fn columns(
+        &mut self,
+        columns: Vec<Value>,
+        definitions: &Map<String, Value>,
+        dir: &Path,
+        depth: usize,
+    ) -> Result<Vec<Value>, GenerateError> {
+        let mut resolved = Vec::with_capacity(columns.len());
+        for column in columns {
+            let Value::Object(mut column) = column else {
+                resolved.push(column);
+                continue;
+            };
+            if let Some(path) = column.shift_remove("$include") {
+                let path = path.as_str().ok_or_else(|| invalid("'$include' in columns must be a path"))?;
+                if !column.is_empty() {
+                    return Err(invalid(format!("Columns included from '{}' can't be overridden", path)));
+                }
+                let (included, _) = self.include(path, dir)?;
+                if let Some(Value::Array(columns)) = included.get("columns") {
+                    resolved.extend(columns.iter().cloned());
+                }
+                continue;
+            }
+            resolved.extend(self.column(column, definitions, dir, depth)?);
+        }
+        Ok(resolved)
+    }
+
+    /// Expands one column's `$ref`, and those of its nested columns. A
+    /// template that is a list of columns expands to all of them.
+    fn column(
+        &mut self,
+        mut column: Map<String, Value>,
+        definitions: &Map<String, Value>,
+        dir: &Path,
+        depth: usize,
+    ) -> Result<Vec<Value>, GenerateError> {
+        if depth > MAX_DEPTH {
+            return Err(invalid("Column templates refer to each other in a cycle"));
+        }
+        if let Some(reference) = column.shift_remove("$ref") {
+            let name = reference.as_str().ok_or_else(|| invalid("'$ref' must be the name of a definition"))?;
+            let name = name.strip_prefix("#/definitions/").unwrap_or(name);
+            let template = definitions.get(name).ok_or_else(|| invalid(format!("Unknown definition '{}'", name)))?;
+            match template {
+                Value::Array(columns) if column.is_empty() => {
+                    return self.columns(columns.clone(), definitions, dir, depth + 1);
+                }
+                Value::Array(_) => {
+                    return Err(invalid(format!("Definition '{}' is a list of columns and can't be overridden", name)));
+                }
+                Value::Object(template) => {
+                    let mut base = match self.column(template.clone(), definitions, dir, depth + 1)?.pop() {
+                        Some(Value::Object(base)) => base,
+                        _ => Map::new(),
+                    };
+                    // The column's name leads, as it would in a column written out in full.
+                    let mut merged = Map::new();
+                    let template_name = base.shift_remove("name");
+                    if let Some(name) = column.shift_remove("name").or(template_name) {
+                        merged.insert("name".to_string(), name);
+                    }
+                    merged.append(&mut base);
+                    for (key, value) in column {
+                        override_value(merged.entry(key).or_insert(Value::Null), value);
+                    }
+                    column = merged;
+                }
+                _ => return Err(invalid(format!("Definition '{}' must be a column or a list of columns", name))),
+            }
+        }
+
+        if let Some(Value::Array(fields)) = column.get_mut("columns") {
+            *fields = self.columns(std::mem::take(fields), definitions, dir, depth)?;
+        }
+        if let Some(items @ Value::Object(_)) = column.get_mut("items") {
+            let Value::Object(template) = std::mem::take(items) else {
+                unreachable!("matched an object above");
+            };
+            *items = self.column(template, definitions, dir, depth)?.pop().unwrap_or(Value::Null);
+        }
+        Ok(vec![Value::Object(column)])
+    }
+}
+
+/// Overrides `base` with `value`, merging objects key by key so that, for
+/// example, `{ "args": { "range": { "end": 10 } } }` keeps the template's `start`.
+fn override_value(base: &mut Value, value: Value) {
+    match (base, value) {
+        (Value::Object(base), Value::Object(value)) => {
+            for (key, value) in value {
+                override_value(base.entry(key).or_insert(Value::Null), value);
+            }
+        }
+        (base, value) => *base = value,
+    }
+}
+
+fn invalid(message: impl Into<String>) -> GenerateError {
+    GenerateError::InvalidArgument(message.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// A fresh directory holding `files`, for tests that include other schemas.
+    fn schema_dir(name: &str, files: &[(&str, Value)]) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("rsfake-compose-{}", name));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        for (file, content) in files {
+            std::fs::write(dir.join(file), content.to_string()).unwrap();
+        }
+        dir
+    }
+
+    fn error(result: Result<Value, GenerateError>) -> String {
+        result.unwrap_err().to_string()
+    }
+
+    #[test]
+    fn ref_overrides_merge_key_by_key() {
+        let schema = json!({
+            "definitions": {
+                "money": { "name": "amount", "type": "f64", "args": { "range": { "start": 0, "end": 100 } } }
+            },
+            "columns": [
+                { "$ref": "money" },
+                { "name": "total", "$ref": "#/definitions/money", "args": { "range": { "end": 500 } }, "null_ratio": 0.1 }
+            ]
+        });
+        let resolved = resolve(schema, Path::new(".")).unwrap();
+        assert_eq!(
+            resolved["columns"],
+            json!([
+                { "name": "amount", "type": "f64", "args": { "range": { "start": 0, "end": 100 } } },
+                { "name": "total", "type": "f64", "args": { "range": { "start": 0, "end": 500 } }, "null_ratio": 0.1 }
+            ])
+        );
+        assert!(resolved.get("definitions").is_none());
+    }
+
+    #[test]
+    fn refs_chain_and_splice_lists() {
+        let schema = json!({
+            "definitions": {
+                "id": { "type": "Sequence" },
+                "user_id": { "$ref": "id", "name": "user_id" },
+                "keys": [{ "$ref": "user_id" }, { "name": "tenant", "type": "Word" }]
+            },
+            "columns": [
+                { "$ref": "keys" },
+                { "name": "tags", "type": "Array", "items": { "$ref": "id" } }
+            ]
+        });
+        let resolved = resolve(schema, Path::new(".")).unwrap();
+        assert_eq!(
+            resolved["columns"],
+            json!([
+                { "name": "user_id", "type": "Sequence" },
+                { "name": "tenant", "type": "Word" },
+                { "name": "tags", "type": "Array", "items": { "type": "Sequence" } }
+            ])
+        );
+    }
+
+    #[test]
+    fn ref_errors() {
+        let unknown = json!({ "columns": [{ "$ref": "missing" }] });
+        assert!(error(resolve(unknown, Path::new("."))).contains("Unknown definition 'missing'"));
+        let overridden_list = json!({
+            "definitions": { "keys": [{ "name": "id", "type": "Sequence" }] },
+            "columns": [{ "$ref": "keys", "name": "id" }]
+        });
+        assert!(error(resolve(overridden_list, Path::new("."))).contains("can't be overridden"));
+    }
+
+    #[test]
+    fn ref_cycles_hit_the_depth_limit() {
+        let direct = json!({
+            "definitions": { "a": { "$ref": "a" } },
+            "columns": [{ "$ref": "a" }]
+        });
+        assert!(error(resolve(direct, Path::new("."))).contains("cycle"));
+        let mutual = json!({
+            "definitions": { "a": { "$ref": "b" }, "b": [{ "$ref": "a" }] },
+            "columns": [{ "$ref": "a" }]
+        });
+        assert!(error(resolve(mutual, Path::new("."))).contains("cycle"));
+    }
+
+    #[test]
+    fn ref_chains_up_to_the_depth_limit_resolve() {
+        let mut definitions = Map::new();
+        definitions.insert("t0".to_string(), json!({ "name": "deep", "type": "Word" }));
+        for i in 1..=MAX_DEPTH {
+            definitions.insert(format!("t{}", i), json!({ "$ref": format!("t{}", i - 1) }));
+        }
+        let chain = |top: usize| json!({ "definitions": definitions, "columns": [{ "$ref": format!("t{}", top) }] });
+        let resolved = resolve(chain(MAX_DEPTH - 1), Path::new(".")).unwrap();
+        assert_eq!(resolved["columns"], json!([{ "name": "deep", "type": "Word" }]));
+        assert!(error(resolve(chain(MAX_DEPTH), Path::new("."))).contains("cycle"));
+    }
+
+    #[test]
+    fn includes_bring_definitions_and_columns() {
+        let dir = schema_dir(
+            "includes",
+            &[
+                ("types.json", json!({ "definitions": { "money": { "type": "f64" }, "code": { "type": "Word" } } })),
+                ("audit.json", json!([{ "name": "created_at", "type": "DateTime" }])),
+            ],
+        );
+        let schema = json!({
+            "$include": "types.json",
+            "definitions": { "code": { "type": "ZipCode" } },
+            "columns": [
+                { "name": "total", "$ref": "money" },
+                { "name": "code", "$ref": "code" },
+                { "$include": "audit.json" }
+            ]
+        });
+        let resolved = resolve(schema, &dir).unwrap();
+        assert_eq!(
+            resolved["columns"],
+            json!([
+                { "name": "total", "type": "f64" },
+                { "name": "code", "type": "ZipCode" },
+                { "name": "created_at", "type": "DateTime" }
+            ])
+        );
+    }
+
+    #[test]
+    fn include_cycles_are_reported() {
+        let dir = schema_dir(
+            "cycle",
+            &[
+                ("a.json", json!({ "$include": "b.json" })),
+                ("b.json", json!({ "$include": "a.json" })),
+            ],
+        );
+        let message = error(resolve(json!({ "$include": "a.json" }), &dir));
+        assert!(message.contains("a.json' is included in a cycle"), "{}", message);
+    }
+
+    #[test]
+    fn included_columns_cant_be_overridden() {
+        let dir = schema_dir("override", &[("audit.json", json!([{ "name": "at", "type": "Date" }]))]);
+        let schema = json!({ "columns": [{ "$include": "audit.json", "name": "other" }] });
+        assert!(error(resolve(schema, &dir)).contains("can't be overridden"));
+    }
+
+    #[test]
+    fn override_value_replaces_non_objects() {
+        let mut base = json!({ "args": { "range": { "start": 1, "end": 2 }, "values": [1, 2] } });
+        override_value(&mut base, json!({ "args": { "range": { "end": 5 }, "values": [3] } }));
+        assert_eq!(base, json!({ "args": { "range": { "start": 1, "end": 5 }, "values": [3] } }));
+    }
+}
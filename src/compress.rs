@@ -0,0 +1,148 @@
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+use bzip2::read::MultiBzDecoder;
+use bzip2::write::BzEncoder;
+use flate2::read::MultiGzDecoder;
+use flate2::write::GzEncoder;
+use serde::Deserialize;
+use xz2::read::XzDecoder;
+use xz2::write::XzEncoder;
+
+/// Stream compression applied to text outputs and detected on inputs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Compression {
+    Gzip,
+    Zstd,
+    Bzip2,
+    Xz,
+}
+
+impl Compression {
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension {
+            "gz" => Some(Compression::Gzip),
+            "zst" => Some(Compression::Zstd),
+            "bz2" => Some(Compression::Bzip2),
+            "xz" => Some(Compression::Xz),
+            _ => None,
+        }
+    }
+
+    /// Detects compression from the final extension of `path`, e.g. `data.csv.gz`.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(Compression::from_extension)
+    }
+}
+
+impl fmt::Display for Compression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Compression::Gzip => "gzip",
+            Compression::Zstd => "zstd",
+            Compression::Bzip2 => "bzip2",
+            Compression::Xz => "xz",
+        })
+    }
+}
+
+impl FromStr for Compression {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "gzip" | "gz" => Ok(Compression::Gzip),
+            "zstd" | "zst" => Ok(Compression::Zstd),
+            "bzip2" | "bz2" => Ok(Compression::Bzip2),
+            "xz" => Ok(Compression::Xz),
+            _ => Err(format!("unknown compression '{}', expected one of: gzip, zstd, bzip2, xz", s)),
+        }
+    }
+}
+
+type Sink = BufWriter<Box<dyn Write + Send>>;
+
+/// A buffered writer that optionally compresses everything written to it.
+///
+/// Call [`OutputStream::finish`] once done so the compressed trailer is
+/// written and any error is reported rather than lost on drop.
+pub enum OutputStream {
+    Plain(Sink),
+    Gzip(GzEncoder<Sink>),
+    Zstd(zstd::Encoder<'static, Sink>),
+    Bzip2(BzEncoder<Sink>),
+    Xz(XzEncoder<Sink>),
+}
+
+impl OutputStream {
+    pub fn create(path: &Path, compression: Option<Compression>) -> io::Result<Self> {
+        Self::new(Box::new(File::create(path)?), compression)
+    }
+
+    pub fn stdout(compression: Option<Compression>) -> io::Result<Self> {
+        Self::new(Box::new(io::stdout()), compression)
+    }
+
+    fn new(writer: Box<dyn Write + Send>, compression: Option<Compression>) -> io::Result<Self> {
+        let sink = BufWriter::new(writer);
+        Ok(match compression {
+            None => OutputStream::Plain(sink),
+            Some(Compression::Gzip) => OutputStream::Gzip(GzEncoder::new(sink, flate2::Compression::default())),
+            Some(Compression::Zstd) => OutputStream::Zstd(zstd::Encoder::new(sink, zstd::DEFAULT_COMPRESSION_LEVEL)?),
+            Some(Compression::Bzip2) => OutputStream::Bzip2(BzEncoder::new(sink, bzip2::Compression::default())),
+            Some(Compression::Xz) => OutputStream::Xz(XzEncoder::new(sink, 6)),
+        })
+    }
+
+    pub fn finish(self) -> io::Result<()> {
+        match self {
+            OutputStream::Plain(mut w) => w.flush(),
+            OutputStream::Gzip(w) => w.finish()?.flush(),
+            OutputStream::Zstd(w) => w.finish()?.flush(),
+            OutputStream::Bzip2(w) => w.finish()?.flush(),
+            OutputStream::Xz(w) => w.finish()?.flush(),
+        }
+    }
+}
+
+impl Write for OutputStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            OutputStream::Plain(w) => w.write(buf),
+            OutputStream::Gzip(w) => w.write(buf),
+            OutputStream::Zstd(w) => w.write(buf),
+            OutputStream::Bzip2(w) => w.write(buf),
+            OutputStream::Xz(w) => w.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            OutputStream::Plain(w) => w.flush(),
+            OutputStream::Gzip(w) => w.flush(),
+            OutputStream::Zstd(w) => w.flush(),
+            OutputStream::Bzip2(w) => w.flush(),
+            OutputStream::Xz(w) => w.flush(),
+        }
+    }
+}
+
+/// Decompresses the whole of `path` into memory. Concatenated streams, as
+/// produced by `cat a.gz b.gz`, are read through to the end.
+pub fn decompress_file(path: &Path, compression: Compression) -> io::Result<Vec<u8>> {
+    let file = BufReader::new(File::open(path)?);
+    let mut data = Vec::new();
+    match compression {
+        Compression::Gzip => MultiGzDecoder::new(file).read_to_end(&mut data)?,
+        Compression::Zstd => zstd::Decoder::new(file)?.read_to_end(&mut data)?,
+        Compression::Bzip2 => MultiBzDecoder::new(file).read_to_end(&mut data)?,
+        Compression::Xz => XzDecoder::new_multi_decoder(file).read_to_end(&mut data)?,
+    };
+    Ok(data)
+}
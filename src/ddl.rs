@@ -0,0 +1,460 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use serde_json::{json, Map, Value};
+use sqlparser::ast::{
+    CharacterLength, ColumnDef, ColumnOption, DataType, EnumMember, ExactNumberInfo, ObjectName, Statement,
+    TableConstraint,
+};
+use sqlparser::dialect::{Dialect, GenericDialect, MySqlDialect, PostgreSqlDialect, SQLiteDialect};
+use sqlparser::parser::{Parser, ParserError};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum DdlError {
+    #[error("SQL parse error: {0}")]
+    Parse(#[from] ParserError),
+    #[error("No CREATE TABLE statements found")]
+    NoTables,
+}
+
+/// SQL dialect used to parse DDL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SqlDialect {
+    Generic,
+    Postgres,
+    MySql,
+    Sqlite,
+}
+
+impl SqlDialect {
+    fn parser_dialect(self) -> Box<dyn Dialect> {
+        match self {
+            SqlDialect::Generic => Box::new(GenericDialect {}),
+            SqlDialect::Postgres => Box::new(PostgreSqlDialect {}),
+            SqlDialect::MySql => Box::new(MySqlDialect {}),
+            SqlDialect::Sqlite => Box::new(SQLiteDialect {}),
+        }
+    }
+}
+
+impl FromStr for SqlDialect {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "generic" => Ok(SqlDialect::Generic),
+            "postgres" | "postgresql" => Ok(SqlDialect::Postgres),
+            "mysql" => Ok(SqlDialect::MySql),
+            "sqlite" => Ok(SqlDialect::Sqlite),
+            _ => Err(format!("unknown SQL dialect '{}', expected generic, postgres, mysql or sqlite", s)),
+        }
+    }
+}
+
+/// The schema imported from one `CREATE TABLE` statement.
+#[derive(Debug)]
+pub struct SqlTable {
+    pub name: String,
+    pub schema: Value,
+    /// Columns whose SQL type had no close equivalent and fell back to `Word`.
+    pub warnings: Vec<String>,
+}
+
+/// Builds a schema for each `CREATE TABLE` in `sql`, mapping SQL types to
+/// generators and ranges. `VARCHAR(n)` and `CHAR(n)` set `max_length`,
+/// `SERIAL`, `AUTO_INCREMENT` and identity columns become a `Sequence`,
+/// single-column `PRIMARY KEY` and `UNIQUE` constraints set `unique`, and
+/// `REFERENCES` are recorded as `references`, copying the referenced column's
+/// type when that table is in `sql` too. Columns without `NOT NULL` get
+/// `null_ratio` when it's above zero.
+///
+/// ```
+/// use rsfake::ddl::{schemas_from_sql, SqlDialect};
+///
+/// let tables = schemas_from_sql(
+///     "CREATE TABLE users (id SERIAL PRIMARY KEY, name VARCHAR(50) NOT NULL, balance NUMERIC(9, 2));",
+///     SqlDialect::Postgres,
+///     0.1,
+/// )?;
+/// let columns = &tables[0].schema["columns"];
+/// assert_eq!(columns[0]["type"], "Sequence");
+/// assert_eq!(columns[0]["unique"], true);
+/// assert_eq!(columns[1]["max_length"], 50);
+/// assert_eq!(columns[2]["type"], "decimal128");
+/// assert_eq!(columns[2]["args"]["scale"], 2);
+/// assert_eq!(columns[2]["null_ratio"], 0.1);
+/// # Ok::<(), rsfake::ddl::DdlError>(())
+/// ```
+pub fn schemas_from_sql(sql: &str, dialect: SqlDialect, null_ratio: f64) -> Result<Vec<SqlTable>, DdlError> {
+    let statements = Parser::parse_sql(dialect.parser_dialect().as_ref(), sql)?;
+    let mut tables: Vec<ImportedTable> = statements
+        .iter()
+        .filter_map(|statement| match statement {
+            Statement::CreateTable(create) => Some(create),
+            _ => None,
+        })
+        .map(|create| {
+            let table = last_ident(&create.name);
+            let mut warnings = Vec::new();
+            let columns = create
+                .columns
+                .iter()
+                .map(|column| {
+                    import_column(column, &create.constraints, null_ratio).unwrap_or_else(|def| {
+                        warnings.push(format!(
+                            "{}.{}: no generator for SQL type {}, using Word",
+                            table, column.name.value, column.data_type
+                        ));
+                        def
+                    })
+                })
+                .collect();
+            ImportedTable { name: table, columns, warnings }
+        })
+        .collect();
+    if tables.is_empty() {
+        return Err(DdlError::NoTables);
+    }
+
+    // Foreign keys generate values shaped like the column they reference.
+    let referenced: HashMap<String, Map<String, Value>> = tables
+        .iter()
+        .flat_map(|table| {
+            table.columns.iter().map(move |def| {
+                (format!("{}.{}", table.name, def["name"].as_str().unwrap_or_default()), def.clone())
+            })
+        })
+        .collect();
+    for table in &mut tables {
+        for def in table.columns.iter_mut() {
+            let Some(target) = def.get("references").and_then(Value::as_str).and_then(|r| referenced.get(r)) else {
+                continue;
+            };
+            const SHAPE: [&str; 4] = ["name", "type", "args", "max_length"];
+            let mut copied = Map::new();
+            copied.insert("name".to_string(), def["name"].clone());
+            for key in &SHAPE[1..] {
+                if let Some(value) = target.get(*key) {
+                    copied.insert(key.to_string(), value.clone());
+                }
+            }
+            copied.extend(def.iter().filter(|(key, _)| !SHAPE.contains(&key.as_str())).map(|(k, v)| (k.clone(), v.clone())));
+            *def = copied;
+        }
+    }
+
+    Ok(tables
+        .into_iter()
+        .map(|table| SqlTable {
+            name: table.name,
+            schema: json!({ "columns": table.columns }),
+            warnings: table.warnings,
+        })
+        .collect())
+}
+
+struct ImportedTable {
+    name: String,
+    columns: Vec<Map<String, Value>>,
+    warnings: Vec<String>,
+}
+
+/// The column definition for `column`, or `Err` with a `Word` fallback when
+/// its SQL type isn't recognised.
+fn import_column(
+    column: &ColumnDef,
+    constraints: &[TableConstraint],
+    null_ratio: f64,
+) -> Result<Map<String, Value>, Map<String, Value>> {
+    let name = &column.name.value;
+    let mut def = Map::new();
+    def.insert("name".to_string(), json!(name));
+    let known = column_type(&column.data_type, &mut def);
+    if !known {
+        def.insert("type".to_string(), json!("Word"));
+    }
+
+    let mut not_null = false;
+    let mut unique = false;
+    let mut references = None;
+    let mut auto_increment = false;
+    for option in &column.options {
+        match &option.option {
+            ColumnOption::DialectSpecific(tokens) => {
+                auto_increment |= tokens.iter().any(|token| {
+                    let token = token.to_string();
+                    token.eq_ignore_ascii_case("AUTO_INCREMENT") || token.eq_ignore_ascii_case("AUTOINCREMENT")
+                });
+            }
+            ColumnOption::Generated { generation_expr: None, .. } | ColumnOption::Identity(_) => auto_increment = true,
+            ColumnOption::NotNull => not_null = true,
+            ColumnOption::Unique { is_primary, .. } => {
+                unique = true;
+                not_null |= *is_primary;
+            }
+            ColumnOption::ForeignKey { foreign_table, referred_columns, .. } => {
+                references = referred_columns.first().map(|c| format!("{}.{}", last_ident(foreign_table), c.value));
+            }
+            _ => {}
+        }
+    }
+    for constraint in constraints {
+        match constraint {
+            TableConstraint::PrimaryKey { columns, .. } if columns.iter().any(|c| &c.value == name) => {
+                not_null = true;
+                unique |= columns.len() == 1;
+            }
+            TableConstraint::Unique { columns, .. } if columns.len() == 1 && &columns[0].value == name => {
+                unique = true;
+            }
+            TableConstraint::ForeignKey { columns, foreign_table, referred_columns, .. } => {
+                if let Some(position) = columns.iter().position(|c| &c.value == name) {
+                    references = referred_columns
+                        .get(position)
+                        .map(|c| format!("{}.{}", last_ident(foreign_table), c.value));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if auto_increment && known {
+        def.insert("type".to_string(), json!("Sequence"));
+        def.shift_remove("args");
+    }
+    if unique {
+        def.insert("unique".to_string(), json!(true));
+    }
+    if let Some(references) = references {
+        def.insert("references".to_string(), json!(references));
+    }
+    if !not_null && null_ratio > 0.0 {
+        def.insert("null_ratio".to_string(), json!(null_ratio));
+    }
+    if known {
+        Ok(def)
+    } else {
+        Err(def)
+    }
+}
+
+/// Sets `type`, `args` and `max_length` for a SQL type, returning false when
+/// there's no sensible generator for it.
+fn column_type(data_type: &DataType, def: &mut Map<String, Value>) -> bool {
+    let mut set = |type_name: &str, args: Option<Value>| {
+        def.insert("type".to_string(), json!(type_name));
+        if let Some(args) = args {
+            def.insert("args".to_string(), args);
+        }
+    };
+    let range = |start: Value, end: Value| Some(json!({ "range": { "start": start, "end": end } }));
+
+    match data_type {
+        // MySQL's conventional boolean.
+        DataType::TinyInt(Some(1)) => set("Boolean", Some(json!({ "ratio": 50 }))),
+        DataType::TinyInt(_) => set("i32", range(json!(i8::MIN), json!(i8::MAX))),
+        DataType::UnsignedTinyInt(_) | DataType::UInt8 => set("u32", range(json!(0), json!(u8::MAX))),
+        DataType::SmallInt(_) | DataType::Int2(_) | DataType::Int16 => {
+            set("i32", range(json!(i16::MIN), json!(i16::MAX)))
+        }
+        DataType::UnsignedSmallInt(_) | DataType::UnsignedInt2(_) | DataType::UInt16 => {
+            set("u32", range(json!(0), json!(u16::MAX)))
+        }
+        DataType::MediumInt(_) => set("i32", range(json!(-(1 << 23)), json!((1 << 23) - 1))),
+        DataType::UnsignedMediumInt(_) => set("u32", range(json!(0), json!((1 << 24) - 1))),
+        DataType::Int(_) | DataType::Integer(_) | DataType::Int4(_) | DataType::Int32 => set("i32", None),
+        DataType::UnsignedInt(_) | DataType::UnsignedInteger(_) | DataType::UnsignedInt4(_) | DataType::UInt32 => {
+            set("u32", None)
+        }
+        DataType::BigInt(_) | DataType::Int8(_) | DataType::Int64 => set("i64", None),
+        DataType::UnsignedBigInt(_) | DataType::UnsignedInt8(_) | DataType::UInt64 => set("u64", None),
+        DataType::Real | DataType::Float4 | DataType::Float32 => set("f32", None),
+        DataType::Float(Some(bits)) if *bits <= 24 => set("f32", None),
+        DataType::Float(_) | DataType::Double | DataType::DoublePrecision | DataType::Float8 | DataType::Float64 => {
+            set("f64", None)
+        }
+        DataType::Numeric(info) | DataType::Decimal(info) | DataType::Dec(info) => match *info {
+            ExactNumberInfo::PrecisionAndScale(precision, scale) => {
+                let (type_name, args) = decimal_type(precision, scale);
+                set(type_name, args)
+            }
+            ExactNumberInfo::Precision(precision) => {
+                let (type_name, args) = decimal_type(precision, 0);
+                set(type_name, args)
+            }
+            ExactNumberInfo::None => set("f64", None),
+        },
+        DataType::Bool | DataType::Boolean => set("Boolean", Some(json!({ "ratio": 50 }))),
+        DataType::Char(length)
+        | DataType::Character(length)
+        | DataType::Varchar(length)
+        | DataType::Nvarchar(length)
+        | DataType::CharVarying(length)
+        | DataType::CharacterVarying(length) => {
+            set("Word", None);
+            if let Some(CharacterLength::IntegerLength { length, .. }) = length {
+                def.insert("max_length".to_string(), json!(length));
+            }
+        }
+        DataType::String(Some(length)) => {
+            set("Word", None);
+            def.insert("max_length".to_string(), json!(length));
+        }
+        DataType::Text
+        | DataType::TinyText
+        | DataType::MediumText
+        | DataType::LongText
+        | DataType::String(None)
+        | DataType::Clob(_) => set("Sentence", None),
+        DataType::Uuid => set("UUIDv4", None),
+        DataType::Date | DataType::Date32 => set("Date", None),
+        DataType::Time(..) => set("Time", None),
+        DataType::Timestamp(..) | DataType::Datetime(_) | DataType::Datetime64(..) => set("DateTime", None),
+        DataType::Interval => set("Duration", None),
+        DataType::Enum(members, _) => {
+            let values: Vec<&str> = members
+                .iter()
+                .map(|member| match member {
+                    EnumMember::Name(name) | EnumMember::NamedValue(name, _) => name.as_str(),
+                })
+                .collect();
+            set("Choice", Some(json!({ "values": values })))
+        }
+        DataType::Set(values) => set("Choice", Some(json!({ "values": values }))),
+        DataType::Custom(name, _) => match last_ident(name).to_ascii_lowercase().as_str() {
+            // Auto-increment keys count up from 1.
+            "serial" | "serial4" | "smallserial" | "serial2" | "bigserial" | "serial8" => set("Sequence", None),
+            "inet" => set("IPv4", None),
+            "macaddr" => set("MACAddress", None),
+            "citext" => set("Word", None),
+            _ => return false,
+        },
+        _ => return false,
+    }
+    true
+}
+
+/// The generator for a `DECIMAL(precision, scale)` column: a native `decimal128`
+/// for precisions up to 38, which polars can hold, and `f64` beyond.
+pub(crate) fn decimal_type(precision: u64, scale: u64) -> (&'static str, Option<Value>) {
+    if (1..=38).contains(&precision) && scale <= precision {
+        ("decimal128", Some(json!({ "precision": precision, "scale": scale })))
+    } else {
+        ("f64", None)
+    }
+}
+
+fn last_ident(name: &ObjectName) -> String {
+    name.0.last().map(|ident| ident.value.clone()).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn columns(sql: &str, dialect: SqlDialect) -> Vec<Value> {
+        let tables = schemas_from_sql(sql, dialect, 0.0).unwrap();
+        tables[0].schema["columns"].as_array().unwrap().clone()
+    }
+
+    fn types(sql: &str, dialect: SqlDialect) -> Vec<String> {
+        columns(sql, dialect).iter().map(|c| c["type"].as_str().unwrap().to_string()).collect()
+    }
+
+    #[test]
+    fn postgres_types() {
+        let sql = "CREATE TABLE t (a SMALLSERIAL, b BIGSERIAL, c INT2, d INT8, e DOUBLE PRECISION, f UUID, \
+                   g TIMESTAMPTZ, h TEXT, i INET, j BOOLEAN, k INTERVAL, l INT GENERATED ALWAYS AS IDENTITY)";
+        assert_eq!(
+            types(sql, SqlDialect::Postgres),
+            ["Sequence", "Sequence", "i32", "i64", "f64", "UUIDv4", "DateTime", "Sentence", "IPv4", "Boolean", "Duration", "Sequence"]
+        );
+    }
+
+    #[test]
+    fn small_integers_are_i32_ranges() {
+        let columns = columns("CREATE TABLE t (a SMALLINT, b SMALLINT UNSIGNED)", SqlDialect::MySql);
+        assert_eq!(columns[0]["type"], "i32");
+        assert_eq!(columns[0]["args"]["range"], json!({ "start": -32768, "end": 32767 }));
+        assert_eq!(columns[1]["type"], "u32");
+        assert_eq!(columns[1]["args"]["range"], json!({ "start": 0, "end": 65535 }));
+    }
+
+    #[test]
+    fn mysql_types() {
+        let sql = "CREATE TABLE t (id INT UNSIGNED AUTO_INCREMENT PRIMARY KEY, flag TINYINT(1), small TINYINT, \
+                   tiny TINYINT UNSIGNED, mid MEDIUMINT, body LONGTEXT, at DATETIME, kind ENUM('a', 'b'), \
+                   price DECIMAL(10, 2))";
+        let columns = columns(sql, SqlDialect::MySql);
+        let types: Vec<&str> = columns.iter().map(|c| c["type"].as_str().unwrap()).collect();
+        assert_eq!(types, ["Sequence", "Boolean", "i32", "u32", "i32", "Sentence", "DateTime", "Choice", "decimal128"]);
+        assert_eq!(columns[2]["args"]["range"], json!({ "start": -128, "end": 127 }));
+        assert_eq!(columns[3]["args"]["range"], json!({ "start": 0, "end": 255 }));
+        assert_eq!(columns[0]["unique"], true);
+        assert!(columns[0].get("args").is_none());
+        assert_eq!(columns[4]["args"]["range"], json!({ "start": -(1 << 23), "end": (1 << 23) - 1 }));
+        assert_eq!(columns[7]["args"]["values"], json!(["a", "b"]));
+        assert_eq!(columns[8]["args"], json!({ "precision": 10, "scale": 2 }));
+    }
+
+    #[test]
+    fn sqlite_types() {
+        let sql = "CREATE TABLE t (id INTEGER PRIMARY KEY AUTOINCREMENT, name VARCHAR(20), score REAL, born DATE)";
+        let columns = columns(sql, SqlDialect::Sqlite);
+        let types: Vec<&str> = columns.iter().map(|c| c["type"].as_str().unwrap()).collect();
+        assert_eq!(types, ["Sequence", "Word", "f32", "Date"]);
+        assert_eq!(columns[1]["max_length"], 20);
+    }
+
+    #[test]
+    fn stored_generated_columns_keep_their_type() {
+        let sql = "CREATE TABLE t (a INT, b INT GENERATED ALWAYS AS (a * 2) STORED)";
+        assert_eq!(types(sql, SqlDialect::MySql), ["i32", "i32"]);
+    }
+
+    #[test]
+    fn decimal_precision_limits() {
+        assert_eq!(decimal_type(38, 10).0, "decimal128");
+        assert_eq!(decimal_type(39, 2), ("f64", None));
+        assert_eq!(decimal_type(0, 0), ("f64", None));
+        assert_eq!(decimal_type(4, 5), ("f64", None));
+        assert_eq!(types("CREATE TABLE t (a NUMERIC(5), b NUMERIC)", SqlDialect::Postgres), ["decimal128", "f64"]);
+    }
+
+    #[test]
+    fn unknown_types_fall_back_to_word_with_a_warning() {
+        let tables = schemas_from_sql("CREATE TABLE t (shape GEOMETRY NOT NULL)", SqlDialect::Postgres, 0.5).unwrap();
+        assert_eq!(tables[0].schema["columns"][0]["type"], "Word");
+        assert!(tables[0].schema["columns"][0].get("null_ratio").is_none());
+        assert_eq!(tables[0].warnings.len(), 1);
+    }
+
+    #[test]
+    fn references_copy_the_target_shape() {
+        let sql = "CREATE TABLE users (code CHAR(8) PRIMARY KEY); \
+                   CREATE TABLE orders (id BIGSERIAL, user_code TEXT REFERENCES users(code), \
+                   owner TEXT, FOREIGN KEY (owner) REFERENCES users(code))";
+        let tables = schemas_from_sql(sql, SqlDialect::Postgres, 0.1).unwrap();
+        let orders = &tables[1].schema["columns"];
+        for column in [&orders[1], &orders[2]] {
+            assert_eq!(column["type"], "Word");
+            assert_eq!(column["max_length"], 8);
+            assert_eq!(column["references"], "users.code");
+            assert_eq!(column["null_ratio"], 0.1);
+        }
+    }
+
+    #[test]
+    fn composite_keys_arent_unique_per_column() {
+        let columns = columns("CREATE TABLE t (a INT, b INT, PRIMARY KEY (a, b), UNIQUE (b))", SqlDialect::Generic);
+        assert!(columns[0].get("unique").is_none());
+        assert_eq!(columns[1]["unique"], true);
+    }
+
+    #[test]
+    fn no_tables_is_an_error() {
+        assert!(matches!(schemas_from_sql("SELECT 1", SqlDialect::Generic, 0.0), Err(DdlError::NoTables)));
+        assert!("oracle".parse::<SqlDialect>().is_err());
+    }
+}
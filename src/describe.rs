@@ -0,0 +1,363 @@
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::str::FromStr;
+
+use polars::prelude::*;
+use rayon::prelude::*;
+use serde::Serialize;
+use serde_json::{json, Value};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum DescribeError {
+    #[error("Polars error: {0}")]
+    Polars(#[from] polars::error::PolarsError),
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// Number of most frequent values listed per column unless overridden.
+pub const DEFAULT_TOP_K: usize = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Text,
+    Json,
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(ReportFormat::Text),
+            "json" => Ok(ReportFormat::Json),
+            _ => Err(format!("unknown report format '{}', expected text or json", s)),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ColumnStats {
+    pub name: String,
+    pub dtype: String,
+    pub count: usize,
+    pub null_count: usize,
+    pub distinct_count: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mean: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub std: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quantiles: Option<Quantiles>,
+    pub top_values: Vec<ValueCount>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub string_length: Option<LengthStats>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Quantiles {
+    pub p25: Option<f64>,
+    pub p50: Option<f64>,
+    pub p75: Option<f64>,
+    pub p95: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ValueCount {
+    pub value: String,
+    pub count: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LengthStats {
+    pub min: usize,
+    pub max: usize,
+    pub mean: f64,
+    pub p50: usize,
+}
+
+/// Profiles every column of `df`, listing its `top_k` most frequent values.
+///
+/// ```
+/// use polars::prelude::*;
+/// use rsfake::describe::describe_dataframe;
+///
+/// let df = df!("tier" => [Some("gold"), Some("gold"), None, Some("silver")])?;
+/// let stats = describe_dataframe(&df, 1)?;
+/// assert_eq!(stats[0].null_count, 1);
+/// assert_eq!(stats[0].distinct_count, 2);
+/// assert_eq!(stats[0].top_values[0].value, "gold");
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn describe_dataframe(df: &DataFrame, top_k: usize) -> Result<Vec<ColumnStats>, DescribeError> {
+    df.get_columns()
+        .par_iter()
+        .map(|series| describe_column(series, top_k))
+        .collect()
+}
+
+fn describe_column(series: &Series, top_k: usize) -> Result<ColumnStats, DescribeError> {
+    let dtype_name = series.dtype().to_string();
+    // Polars supports few operations on decimals, so they're described as floats.
+    let floats;
+    let series = if let DataType::Decimal(..) = series.dtype() {
+        floats = series.cast(&DataType::Float64)?;
+        &floats
+    } else {
+        series
+    };
+    // Lists and structs have no order, and polars can't count or cast them,
+    // so their distinct and top values are taken from their text form.
+    let nested = matches!(series.dtype(), DataType::List(_) | DataType::Struct(_));
+    let non_null = series.drop_nulls();
+    let strings = if nested {
+        non_null.iter().map(|value| Some(value.to_string())).collect::<StringChunked>()
+    } else {
+        non_null.cast(&DataType::String)?.str()?.clone()
+    };
+    let mut stats = ColumnStats {
+        name: series.name().to_string(),
+        dtype: dtype_name,
+        count: series.len(),
+        null_count: series.null_count(),
+        distinct_count: if nested { strings.n_unique()? } else { non_null.n_unique()? },
+        min: (!nested)
+            .then(|| series.min_reduce().ok())
+            .flatten()
+            .map(|s| any_value_to_json(s.value()))
+            .filter(|v| !v.is_null()),
+        max: (!nested)
+            .then(|| series.max_reduce().ok())
+            .flatten()
+            .map(|s| any_value_to_json(s.value()))
+            .filter(|v| !v.is_null()),
+        mean: None,
+        std: None,
+        quantiles: None,
+        top_values: Vec::new(),
+        string_length: None,
+    };
+
+    let dtype = series.dtype();
+    if dtype.is_numeric() || dtype == &DataType::Boolean {
+        let values = series.cast(&DataType::Float64)?;
+        let values = values.f64()?;
+        stats.mean = values.mean();
+        stats.std = values.std(1);
+        if dtype.is_numeric() {
+            let quantile = |q| values.quantile(q, QuantileInterpolOptions::Linear);
+            stats.quantiles = Some(Quantiles {
+                p25: quantile(0.25)?,
+                p50: quantile(0.5)?,
+                p75: quantile(0.75)?,
+                p95: quantile(0.95)?,
+            });
+        }
+    }
+
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for value in strings.into_no_null_iter() {
+        *counts.entry(value).or_default() += 1;
+    }
+    let mut counts: Vec<(&str, usize)> = counts.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    stats.top_values = counts
+        .into_iter()
+        .take(top_k)
+        .map(|(value, count)| ValueCount { value: value.to_string(), count })
+        .collect();
+
+    if dtype == &DataType::String && !strings.is_empty() {
+        let mut lengths: Vec<usize> = strings.into_no_null_iter().map(|s| s.chars().count()).collect();
+        lengths.sort_unstable();
+        stats.string_length = Some(LengthStats {
+            min: lengths[0],
+            max: lengths[lengths.len() - 1],
+            mean: lengths.iter().sum::<usize>() as f64 / lengths.len() as f64,
+            p50: lengths[lengths.len() / 2],
+        });
+    }
+
+    Ok(stats)
+}
+
+fn any_value_to_json(value: &AnyValue) -> Value {
+    match value {
+        AnyValue::Null => Value::Null,
+        AnyValue::Boolean(b) => json!(b),
+        AnyValue::UInt8(v) => json!(v),
+        AnyValue::UInt16(v) => json!(v),
+        AnyValue::UInt32(v) => json!(v),
+        AnyValue::UInt64(v) => json!(v),
+        AnyValue::Int8(v) => json!(v),
+        AnyValue::Int16(v) => json!(v),
+        AnyValue::Int32(v) => json!(v),
+        AnyValue::Int64(v) => json!(v),
+        AnyValue::Float32(v) => json!(v),
+        AnyValue::Float64(v) => json!(v),
+        AnyValue::String(s) => json!(s),
+        AnyValue::StringOwned(s) => json!(s.as_str()),
+        other => json!(other.to_string()),
+    }
+}
+
+/// Renders `stats` as an indented text report or pretty-printed JSON.
+///
+/// ```
+/// use polars::prelude::*;
+/// use rsfake::describe::{describe_dataframe, render_report, ReportFormat};
+///
+/// let df = df!("n" => [1u32, 2, 3])?;
+/// let report = render_report(&describe_dataframe(&df, 3)?, ReportFormat::Text)?;
+/// assert!(report.starts_with("n (u32)"));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn render_report(stats: &[ColumnStats], format: ReportFormat) -> Result<String, DescribeError> {
+    if format == ReportFormat::Json {
+        return Ok(serde_json::to_string_pretty(stats)? + "\n");
+    }
+
+    let fmt_value = |v: &Option<Value>| match v {
+        Some(Value::String(s)) => s.clone(),
+        Some(v) => v.to_string(),
+        None => "-".to_string(),
+    };
+    let fmt_float = |v: Option<f64>| v.map_or("-".to_string(), |v| format!("{:.4}", v));
+
+    let mut out = String::new();
+    for column in stats {
+        let _ = writeln!(out, "{} ({})", column.name, column.dtype);
+        let _ = writeln!(
+            out,
+            "  count: {}, nulls: {}, distinct: {}",
+            column.count, column.null_count, column.distinct_count
+        );
+        let _ = writeln!(out, "  min: {}, max: {}", fmt_value(&column.min), fmt_value(&column.max));
+        if column.mean.is_some() {
+            let _ = writeln!(out, "  mean: {}, std: {}", fmt_float(column.mean), fmt_float(column.std));
+        }
+        if let Some(q) = &column.quantiles {
+            let _ = writeln!(
+                out,
+                "  p25: {}, p50: {}, p75: {}, p95: {}",
+                fmt_float(q.p25),
+                fmt_float(q.p50),
+                fmt_float(q.p75),
+                fmt_float(q.p95)
+            );
+        }
+        if !column.top_values.is_empty() {
+            let top: Vec<String> = column
+                .top_values
+                .iter()
+                .map(|v| format!("{} ({})", v.value, v.count))
+                .collect();
+            let _ = writeln!(out, "  top values: {}", top.join(", "));
+        }
+        if let Some(len) = &column.string_length {
+            let _ = writeln!(
+                out,
+                "  string length: min {}, max {}, mean {:.2}, median {}",
+                len.min, len.max, len.mean, len.p50
+            );
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn describe(series: Series) -> ColumnStats {
+        describe_column(&series, 3).unwrap()
+    }
+
+    fn top(stats: &ColumnStats) -> Vec<(&str, usize)> {
+        stats.top_values.iter().map(|v| (v.value.as_str(), v.count)).collect()
+    }
+
+    #[test]
+    fn numeric_columns() {
+        let stats = describe(Series::new("n", &[Some(1i32), Some(2), Some(2), Some(5), None]));
+        assert_eq!((stats.count, stats.null_count, stats.distinct_count), (5, 1, 3));
+        assert_eq!(stats.min, Some(json!(1)));
+        assert_eq!(stats.max, Some(json!(5)));
+        assert_eq!(stats.mean, Some(2.5));
+        assert_eq!(stats.quantiles.as_ref().unwrap().p50, Some(2.0));
+        assert_eq!(top(&stats), [("2", 2), ("1", 1), ("5", 1)]);
+        assert!(stats.string_length.is_none());
+    }
+
+    #[test]
+    fn string_columns() {
+        let stats = describe(Series::new("s", &["ab", "abcd", "ab", "x"]));
+        assert_eq!(stats.distinct_count, 3);
+        assert_eq!(stats.min, Some(json!("ab")));
+        assert_eq!(stats.max, Some(json!("x")));
+        assert!(stats.mean.is_none());
+        assert_eq!(top(&stats)[0], ("ab", 2));
+        let length = stats.string_length.as_ref().unwrap();
+        assert_eq!((length.min, length.max, length.mean, length.p50), (1, 4, 2.25, 2));
+    }
+
+    #[test]
+    fn boolean_columns() {
+        let stats = describe(Series::new("b", &[true, true, false, true]));
+        assert_eq!(stats.distinct_count, 2);
+        assert_eq!(stats.mean, Some(0.75));
+        assert!(stats.quantiles.is_none());
+        assert_eq!(top(&stats), [("true", 3), ("false", 1)]);
+    }
+
+    #[test]
+    fn all_null_columns() {
+        let stats = describe(Series::new("n", &[None::<f64>, None]));
+        assert_eq!((stats.count, stats.null_count, stats.distinct_count), (2, 2, 0));
+        assert_eq!(stats.min, None);
+        assert_eq!(stats.mean, None);
+        assert!(stats.top_values.is_empty());
+        let stats = describe(Series::new("s", &[None::<&str>]));
+        assert!(stats.string_length.is_none());
+    }
+
+    #[test]
+    fn list_columns() {
+        let a = Series::new("", &["x", "y"]);
+        let b = Series::new("", &["z"]);
+        let lists = Series::new("tags", &[a.clone(), b, a]);
+        let stats = describe(lists);
+        assert_eq!(stats.distinct_count, 2);
+        assert_eq!(stats.min, None);
+        assert_eq!(stats.max, None);
+        assert_eq!(stats.top_values[0].count, 2);
+        assert!(stats.top_values[0].value.contains('x'));
+    }
+
+    #[test]
+    fn struct_columns() {
+        let fields = df!("id" => [1i32, 2, 1], "name" => ["a", "b", "a"]).unwrap();
+        let structs = fields.into_struct("user").into_series();
+        let stats = describe(structs);
+        assert_eq!(stats.distinct_count, 2);
+        assert_eq!(stats.min, None);
+        assert_eq!(stats.top_values[0].count, 2);
+        let report = render_report(&[stats], ReportFormat::Text).unwrap();
+        assert!(report.starts_with("user (struct[2])"), "{}", report);
+    }
+
+    #[test]
+    fn decimal_columns_keep_their_dtype_name() {
+        let decimals = Int128Chunked::from_vec("d", vec![150, 275])
+            .into_decimal_unchecked(Some(5), 2)
+            .into_series();
+        let stats = describe(decimals);
+        assert_eq!(stats.dtype, "decimal[5,2]");
+        assert_eq!(stats.max, Some(json!(2.75)));
+    }
+}
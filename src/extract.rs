@@ -1,96 +1,1131 @@
 use std::fs::{self, File};
-use std::io::BufWriter;
-use std::path::Path;
+use std::io::{BufWriter, Cursor, Read, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
+use polars::io::mmap::MmapBytesReader;
 use polars::prelude::*;
+use polars_core::utils::try_get_supertype;
+use polars_parquet::write::{
+    row_group_iter, to_parquet_schema, transverse, Encoding, FileWriter, Version, WriteOptions,
+};
+use rust_xlsxwriter::{ExcelDateTime, Format, Workbook, Worksheet, XlsxError};
+use serde::Deserialize;
+use serde_json::{Map, Value};
 use thiserror::Error;
 
+use crate::compress::{decompress_file, Compression, OutputStream};
+
+/// Maximum number of rows in an Excel worksheet, including the header row.
+const XLSX_MAX_ROWS: usize = 1_048_576;
+
+/// Days between the Excel epoch (1899-12-30) and the Unix epoch.
+const XLSX_UNIX_EPOCH_OFFSET: f64 = 25_569.0;
+
 #[derive(Error, Debug)]
 pub enum ExtractError {
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
     #[error("Polars error: {0}")]
     Polars(#[from] polars::error::PolarsError),
+    #[error("Excel error: {0}")]
+    Xlsx(#[from] XlsxError),
     #[error("Unsupported file format: {0}")]
     UnsupportedFormat(String),
+    #[error("Invalid option: {0}")]
+    InvalidOption(String),
+    #[error("Invalid glob pattern: {0}")]
+    Pattern(#[from] glob::PatternError),
+    #[error("Schema mismatch: {0}")]
+    SchemaMismatch(String),
+    #[error("Format mismatch: {0}")]
+    FormatMismatch(String),
+    #[error("No data files found in {0}")]
+    NoFilesFound(String),
+}
+
+/// Quoting behaviour used when writing CSV files.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CsvQuoteStyle {
+    #[default]
+    Necessary,
+    Always,
+    NonNumeric,
+    Never,
+}
+
+impl FromStr for CsvQuoteStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "necessary" => Ok(CsvQuoteStyle::Necessary),
+            "always" => Ok(CsvQuoteStyle::Always),
+            "non_numeric" => Ok(CsvQuoteStyle::NonNumeric),
+            "never" => Ok(CsvQuoteStyle::Never),
+            _ => Err(format!("unknown quote style '{}', expected one of: necessary, always, non_numeric, never", s)),
+        }
+    }
+}
+
+impl From<CsvQuoteStyle> for QuoteStyle {
+    fn from(style: CsvQuoteStyle) -> Self {
+        match style {
+            CsvQuoteStyle::Necessary => QuoteStyle::Necessary,
+            CsvQuoteStyle::Always => QuoteStyle::Always,
+            CsvQuoteStyle::NonNumeric => QuoteStyle::NonNumeric,
+            CsvQuoteStyle::Never => QuoteStyle::Never,
+        }
+    }
+}
+
+/// CSV dialect used for reading and writing delimited files.
+///
+/// `delimiter` is left unset by default so that the `tsv` format can fall
+/// back to a tab while `csv` falls back to a comma.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct CsvOptions {
+    pub delimiter: Option<char>,
+    pub quote_char: char,
+    pub quote_style: CsvQuoteStyle,
+    pub header: bool,
+    pub null_value: String,
+    pub line_terminator: String,
+    pub date_format: Option<String>,
+    pub time_format: Option<String>,
+    pub datetime_format: Option<String>,
+    pub bom: bool,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        CsvOptions {
+            delimiter: None,
+            quote_char: '"',
+            quote_style: CsvQuoteStyle::default(),
+            header: true,
+            null_value: String::new(),
+            line_terminator: "\n".to_string(),
+            date_format: None,
+            time_format: None,
+            datetime_format: None,
+            bom: false,
+        }
+    }
+}
+
+impl CsvOptions {
+    fn separator(&self, default: char) -> Result<u8, ExtractError> {
+        ascii_byte(self.delimiter.unwrap_or(default), "delimiter")
+    }
+
+    fn quote_byte(&self) -> Result<u8, ExtractError> {
+        ascii_byte(self.quote_char, "quote_char")
+    }
+
+    fn eol_byte(&self) -> Result<u8, ExtractError> {
+        self.line_terminator
+            .bytes()
+            .last()
+            .ok_or_else(|| ExtractError::InvalidOption("'line_terminator' must not be empty".to_string()))
+    }
+}
+
+/// Compression codec used for Parquet data pages.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ParquetCodec {
+    None,
+    Snappy,
+    Gzip,
+    Lz4,
+    Brotli,
+    #[default]
+    Zstd,
+}
+
+impl FromStr for ParquetCodec {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" | "uncompressed" => Ok(ParquetCodec::None),
+            "snappy" => Ok(ParquetCodec::Snappy),
+            "gzip" => Ok(ParquetCodec::Gzip),
+            "lz4" => Ok(ParquetCodec::Lz4),
+            "brotli" => Ok(ParquetCodec::Brotli),
+            "zstd" => Ok(ParquetCodec::Zstd),
+            _ => Err(format!("unknown compression '{}', expected one of: none, snappy, gzip, lz4, brotli, zstd", s)),
+        }
+    }
+}
+
+/// Parquet writer tuning. `None` leaves the Polars default in place.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ParquetOptions {
+    pub compression: ParquetCodec,
+    pub compression_level: Option<i32>,
+    pub row_group_size: Option<usize>,
+    pub data_page_size: Option<usize>,
+    pub statistics: bool,
+    pub dictionary: bool,
+    pub partition_by: Vec<String>,
+    pub max_rows_per_file: Option<usize>,
+}
+
+impl Default for ParquetOptions {
+    fn default() -> Self {
+        ParquetOptions {
+            compression: ParquetCodec::default(),
+            compression_level: None,
+            row_group_size: None,
+            data_page_size: None,
+            statistics: true,
+            dictionary: true,
+            partition_by: Vec::new(),
+            max_rows_per_file: None,
+        }
+    }
+}
+
+impl ParquetOptions {
+    fn compression(&self) -> Result<ParquetCompression, ExtractError> {
+        let level = self.compression_level;
+        let unsupported_level = |codec: &str| {
+            ExtractError::InvalidOption(format!("'{}' compression does not accept a level", codec))
+        };
+        Ok(match self.compression {
+            ParquetCodec::None if level.is_some() => return Err(unsupported_level("none")),
+            ParquetCodec::Snappy if level.is_some() => return Err(unsupported_level("snappy")),
+            ParquetCodec::Lz4 if level.is_some() => return Err(unsupported_level("lz4")),
+            ParquetCodec::None => ParquetCompression::Uncompressed,
+            ParquetCodec::Snappy => ParquetCompression::Snappy,
+            ParquetCodec::Lz4 => ParquetCompression::Lz4Raw,
+            ParquetCodec::Gzip => ParquetCompression::Gzip(
+                level.map(|l| GzipLevel::try_new(l.try_into().unwrap_or(u8::MAX))).transpose()?,
+            ),
+            ParquetCodec::Brotli => ParquetCompression::Brotli(
+                level.map(|l| BrotliLevel::try_new(l.try_into().unwrap_or(u32::MAX))).transpose()?,
+            ),
+            ParquetCodec::Zstd => ParquetCompression::Zstd(level.map(ZstdLevel::try_new).transpose()?),
+        })
+    }
+}
+
+/// Writer settings taken from the schema-level `output` section.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct OutputOptions {
+    pub compression: Option<Compression>,
+    pub csv: CsvOptions,
+    pub parquet: ParquetOptions,
+}
+
+impl OutputOptions {
+    /// The settings for the output written to `path` in `format`: those shared
+    /// by the `output` section, with the ones of the `outputs` entry for `path`
+    /// merged over them. The shared `compression` only applies to formats that
+    /// are written as a stream.
+    ///
+    /// ```
+    /// use rsfake::OutputOptions;
+    /// use serde_json::json;
+    ///
+    /// let section = json!({
+    ///     "compression": "gzip",
+    ///     "csv": { "delimiter": "|", "header": false },
+    ///     "outputs": [{ "path": "eu.csv", "csv": { "delimiter": ";" } }],
+    /// });
+    /// let eu = OutputOptions::for_output(Some(&section), "eu.csv", "csv")?;
+    /// assert_eq!((eu.csv.delimiter, eu.csv.header), (Some(';'), false));
+    /// assert!(eu.compression.is_some());
+    /// let parquet = OutputOptions::for_output(Some(&section), "all.parquet", "parquet")?;
+    /// assert!(parquet.compression.is_none());
+    /// # Ok::<(), rsfake::ExtractError>(())
+    /// ```
+    pub fn for_output(section: Option<&Value>, path: &str, format: &str) -> Result<Self, ExtractError> {
+        let Some(Value::Object(section)) = section else {
+            return Ok(OutputOptions::default());
+        };
+        let mut merged = section.clone();
+        merged.shift_remove("outputs");
+        if !is_stream_format(format) {
+            merged.shift_remove("compression");
+        }
+        if let Some(entry) = listed_outputs(section)?.into_iter().find(|entry| entry.path == path) {
+            for (key, value) in entry.settings {
+                match (merged.get_mut(&key), value) {
+                    (Some(Value::Object(shared)), Value::Object(own)) => shared.extend(own),
+                    (_, value) => {
+                        merged.insert(key, value);
+                    }
+                }
+            }
+        }
+        serde_json::from_value(Value::Object(merged))
+            .map_err(|e| ExtractError::InvalidOption(format!("invalid output settings for '{}': {}", path, e)))
+    }
+}
+
+/// An entry of the `output` section's `outputs` list.
+#[derive(Debug, Clone)]
+pub struct ListedOutput {
+    pub path: String,
+    pub format: Option<String>,
+    /// The entry's own `compression`, `csv` and `parquet` settings.
+    pub settings: Map<String, Value>,
 }
 
-pub fn read_file(file_path: &str) -> Result<DataFrame, ExtractError> {
+/// The outputs listed in an `output` section, written when no `-o` is given.
+pub fn listed_outputs(section: &Map<String, Value>) -> Result<Vec<ListedOutput>, ExtractError> {
+    let Some(outputs) = section.get("outputs") else {
+        return Ok(Vec::new());
+    };
+    let invalid = || ExtractError::InvalidOption("'outputs' must be a list of objects with a 'path'".to_string());
+    outputs
+        .as_array()
+        .ok_or_else(invalid)?
+        .iter()
+        .map(|entry| {
+            let mut settings = entry.as_object().ok_or_else(invalid)?.clone();
+            let path = settings.shift_remove("path").and_then(|p| p.as_str().map(str::to_string)).ok_or_else(invalid)?;
+            let format = match settings.shift_remove("format") {
+                Some(Value::String(format)) => Some(format),
+                None => None,
+                Some(_) => return Err(invalid()),
+            };
+            Ok(ListedOutput { path, format, settings })
+        })
+        .collect()
+}
+
+/// Whether `format` is written as a stream, which can be compressed and sent to stdout.
+pub fn is_stream_format(format: &str) -> bool {
+    matches!(format, "csv" | "tsv" | "json" | "jsonl" | "ndjson" | "ipc_stream")
+}
+
+fn ascii_byte(c: char, name: &str) -> Result<u8, ExtractError> {
+    if c.is_ascii() {
+        Ok(c as u8)
+    } else {
+        Err(ExtractError::InvalidOption(format!("'{}' must be a single ASCII character, got '{}'", name, c)))
+    }
+}
+
+/// Path that selects stdin for input or stdout for output.
+pub const STDIO_PATH: &str = "-";
+
+/// Reads a file, a directory of Parquet files or a glob of files into one
+/// dataframe. The format and any compression come from the file extensions.
+///
+/// ```
+/// use rsfake::{read_file, write_dataframe, CsvOptions, Generator, OutputOptions, Schema};
+///
+/// let schema = Schema::from_json(r#"{ "columns": [{ "name": "city", "type": "CityName" }] }"#)?;
+/// let mut df = Generator::new(schema).rows(20).generate()?;
+/// let path = std::env::temp_dir().join("rsfake-doc-read.csv.gz");
+/// let path = path.to_str().unwrap();
+/// write_dataframe(&mut df, path, "csv", &OutputOptions::default())?;
+///
+/// let read = read_file(path, &CsvOptions::default())?;
+/// assert!(read.equals(&df));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn read_file(file_path: &str, csv_options: &CsvOptions) -> Result<DataFrame, ExtractError> {
+    // A file whose name happens to contain `[` or `?` is read as it is.
+    if is_glob_pattern(file_path) && !Path::new(file_path).exists() {
+        return read_glob(file_path, csv_options);
+    }
+
     let path = Path::new(file_path);
-    let extension = path.extension().and_then(|ext| ext.to_str()).unwrap_or("");
+    if path.is_dir() {
+        return read_partitioned_parquet(path);
+    }
+
+    let compression = Compression::from_path(path);
+    let extension = format_extension(path);
+    let format = format_from_extension(extension)
+        .ok_or_else(|| ExtractError::UnsupportedFormat(extension.to_string()))?;
+    read_with_format(open_input(path, compression)?, format, csv_options)
+}
+
+/// Reads a dataframe in the given `format` from stdin. Stdin is buffered
+/// into memory first since the Polars readers require a seekable source.
+///
+/// ```no_run
+/// use rsfake::{read_stdin, CsvOptions};
+///
+/// let df = read_stdin("jsonl", &CsvOptions::default())?;
+/// println!("{}", df);
+/// # Ok::<(), rsfake::ExtractError>(())
+/// ```
+pub fn read_stdin(format: &str, csv_options: &CsvOptions) -> Result<DataFrame, ExtractError> {
+    let mut data = Vec::new();
+    std::io::stdin().lock().read_to_end(&mut data)?;
+    read_with_format(Cursor::new(data), format, csv_options)
+}
+
+fn read_with_format<R: MmapBytesReader>(reader: R, format: &str, csv_options: &CsvOptions) -> Result<DataFrame, ExtractError> {
+    match format {
+        "parquet" => read_parquet(reader),
+        "json" => read_json(reader, JsonFormat::Json),
+        "jsonl" | "ndjson" => read_json(reader, JsonFormat::JsonLines),
+        "csv" => read_csv(reader, csv_options, ','),
+        "tsv" => read_csv(reader, csv_options, '\t'),
+        "ipc_stream" => read_ipc_stream(reader),
+        _ => Err(ExtractError::UnsupportedFormat(format.to_string())),
+    }
+}
 
+/// Returns the extension that identifies the data format of `path`, looking
+/// past a compression suffix so `data.csv.gz` yields `csv`.
+fn format_extension(path: &Path) -> &str {
+    let inner_path = match Compression::from_path(path) {
+        Some(_) => Path::new(path.file_stem().unwrap_or_default()),
+        None => path,
+    };
+    inner_path.extension().and_then(|ext| ext.to_str()).unwrap_or("")
+}
+
+fn format_from_extension(extension: &str) -> Option<&'static str> {
     match extension {
-        "parquet" => {
-            if path.is_dir() {
-                read_partitioned_parquet(file_path)
-            } else {
-                read_single_parquet_file(file_path)
-            }
+        "parquet" => Some("parquet"),
+        "json" => Some("json"),
+        "jsonl" | "ndjson" => Some("jsonl"),
+        "csv" => Some("csv"),
+        "tsv" => Some("tsv"),
+        "arrows" => Some("ipc_stream"),
+        "xlsx" => Some("xlsx"),
+        _ => None,
+    }
+}
+
+/// Picks the writer for `file_path`. The format is inferred from the path's
+/// extension(s), with `format` acting as an override. An explicit format
+/// that contradicts a recognised extension is rejected unless `force` is set.
+/// Paths without a recognised extension default to parquet.
+///
+/// ```
+/// use rsfake::extract::resolve_output_format;
+///
+/// assert_eq!(resolve_output_format("users.jsonl.gz", None, false)?, "jsonl");
+/// assert_eq!(resolve_output_format("users", None, false)?, "parquet");
+/// assert!(resolve_output_format("users.csv", Some("json"), false).is_err());
+/// # Ok::<(), rsfake::ExtractError>(())
+/// ```
+pub fn resolve_output_format(file_path: &str, format: Option<&str>, force: bool) -> Result<String, ExtractError> {
+    let inferred = format_from_extension(format_extension(Path::new(file_path)));
+    match (format, inferred) {
+        (Some(format), Some(inferred)) if !force && canonical_format(format) != inferred => {
+            Err(ExtractError::FormatMismatch(format!(
+                "'{}' looks like {} but --format is {}, pass --force to write it anyway",
+                file_path, inferred, format
+            )))
         }
-        "json" => read_json_file(file_path),
-        "csv" => read_csv_file(file_path),
-        _ => Err(ExtractError::UnsupportedFormat(extension.to_string())),
+        (Some(format), _) => Ok(format.to_string()),
+        (None, Some(inferred)) => Ok(inferred.to_string()),
+        (None, None) if file_path == STDIO_PATH => Err(ExtractError::FormatMismatch(
+            "can't infer the format for stdout, pass --format".to_string(),
+        )),
+        (None, None) => Ok("parquet".to_string()),
     }
 }
 
-pub fn write_dataframe(df: &mut DataFrame, file_path: &str, format: &str) -> Result<(), ExtractError> {
+fn canonical_format(format: &str) -> &str {
     match format {
-        "parquet" => write_dataframe_to_parquet(df, file_path),
-        "json" => write_dataframe_to_json(df, file_path),
-        "csv" => write_dataframe_to_csv(df, file_path),
+        "ndjson" => "jsonl",
+        other => other,
+    }
+}
+
+/// Checks that `format` can be written to `file_path` with `options`, before
+/// any data is generated, returning the stream compression to write with.
+/// Parquet and xlsx can't go to stdout or be stream compressed, and a
+/// compression in `options` must agree with the path's extension.
+///
+/// ```
+/// use rsfake::extract::check_output;
+/// use rsfake::OutputOptions;
+///
+/// assert!(check_output("-", "parquet", &OutputOptions::default()).is_err());
+/// assert!(check_output("out.csv.gz", "csv", &OutputOptions::default())?.is_some());
+/// # Ok::<(), rsfake::ExtractError>(())
+/// ```
+pub fn check_output(file_path: &str, format: &str, options: &OutputOptions) -> Result<Option<Compression>, ExtractError> {
+    let compression = match (options.compression, Compression::from_path(Path::new(file_path))) {
+        (Some(chosen), Some(inferred)) if chosen != inferred => {
+            return Err(ExtractError::FormatMismatch(format!(
+                "'{}' looks {} compressed but the compression is {}, so it couldn't be read back",
+                file_path, inferred, chosen
+            )))
+        }
+        (chosen, inferred) => chosen.or(inferred),
+    };
+    match format {
+        "parquet" | "xlsx" if file_path == STDIO_PATH => Err(ExtractError::InvalidOption(format!(
+            "{} output can't be written to stdout, use csv, tsv, json, jsonl or ipc_stream instead",
+            format
+        ))),
+        "parquet" | "xlsx" if compression.is_some() => Err(ExtractError::InvalidOption(format!(
+            "{} output can't be stream compressed, use a csv or json format instead",
+            format
+        ))),
+        "parquet" | "xlsx" | "json" | "jsonl" | "ndjson" | "csv" | "tsv" | "ipc_stream" => Ok(compression),
         _ => Err(ExtractError::UnsupportedFormat(format.to_string())),
     }
 }
 
-fn read_single_parquet_file(file_path: &str) -> Result<DataFrame, ExtractError> {
-    let file = File::open(file_path)?;
-    Ok(ParquetReader::new(file).finish()?)
+/// Writes `df` to `file_path`, or to stdout for [`STDIO_PATH`], in `format`,
+/// after [`check_output`].
+///
+/// ```
+/// use rsfake::{write_dataframe, Generator, OutputOptions, Schema};
+///
+/// let schema = Schema::from_json(r#"{ "columns": [{ "name": "n", "type": "i64" }] }"#)?;
+/// let mut df = Generator::new(schema).rows(10).generate()?;
+/// let path = std::env::temp_dir().join("rsfake-doc-write.parquet");
+/// write_dataframe(&mut df, path.to_str().unwrap(), "parquet", &OutputOptions::default())?;
+/// assert!(path.exists());
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn write_dataframe(
+    df: &mut DataFrame,
+    file_path: &str,
+    format: &str,
+    options: &OutputOptions,
+) -> Result<(), ExtractError> {
+    let compression = check_output(file_path, format, options)?;
+    match format {
+        "parquet" => write_dataframe_to_parquet(df, file_path, &options.parquet),
+        "json" => write_stream(file_path, compression, |w| write_dataframe_to_json(df, w, JsonFormat::Json)),
+        "jsonl" | "ndjson" => write_stream(file_path, compression, |w| {
+            write_dataframe_to_json(df, w, JsonFormat::JsonLines)
+        }),
+        "csv" => write_stream(file_path, compression, |w| write_dataframe_to_csv(df, w, &options.csv, ',')),
+        "tsv" => write_stream(file_path, compression, |w| write_dataframe_to_csv(df, w, &options.csv, '\t')),
+        "ipc_stream" => write_stream(file_path, compression, |w| write_dataframe_to_ipc_stream(df, w)),
+        "xlsx" => write_dataframe_to_xlsx(df, file_path),
+        _ => Err(ExtractError::UnsupportedFormat(format.to_string())),
+    }
+}
+
+fn write_stream<F>(file_path: &str, compression: Option<Compression>, write: F) -> Result<(), ExtractError>
+where
+    F: FnOnce(&mut OutputStream) -> Result<(), ExtractError>,
+{
+    let mut stream = if file_path == STDIO_PATH {
+        OutputStream::stdout(compression)?
+    } else {
+        OutputStream::create(Path::new(file_path), compression)?
+    };
+    write(&mut stream)?;
+    stream.finish()?;
+    Ok(())
+}
+
+/// Opens `path` for reading, decompressing it into memory first if needed
+/// since the Polars readers require a seekable source.
+fn open_input(path: &Path, compression: Option<Compression>) -> Result<Box<dyn MmapBytesReader>, ExtractError> {
+    Ok(match compression {
+        Some(compression) => Box::new(Cursor::new(decompress_file(path, compression)?)),
+        None => Box::new(File::open(path)?),
+    })
+}
+
+fn read_parquet<R: MmapBytesReader>(reader: R) -> Result<DataFrame, ExtractError> {
+    Ok(ParquetReader::new(reader).finish()?)
 }
 
-fn read_partitioned_parquet(base_dir: &str) -> Result<DataFrame, ExtractError> {
+/// Reads every `.parquet` file below `base_dir`, adding a column for each
+/// Hive-style `key=value` directory in the file's path.
+fn read_partitioned_parquet(base_dir: &Path) -> Result<DataFrame, ExtractError> {
+    let mut files = Vec::new();
+    collect_parquet_files(base_dir, &mut files)?;
+
+    let dataframes = files
+        .iter()
+        .map(|path| Ok((path.as_path(), ParquetReader::new(File::open(path)?).finish()?)))
+        .collect::<Result<Vec<_>, ExtractError>>()?;
+
+    if dataframes.is_empty() {
+        return Err(ExtractError::NoFilesFound(base_dir.display().to_string()));
+    }
+    concat_with_partitions(dataframes)
+}
+
+fn read_glob(pattern: &str, csv_options: &CsvOptions) -> Result<DataFrame, ExtractError> {
     let mut dataframes = Vec::new();
+    let mut paths = glob::glob(pattern)?.collect::<Result<Vec<_>, _>>().map_err(std::io::Error::from)?;
+    paths.sort();
+    for path in &paths {
+        let df = read_file(&path.to_string_lossy(), csv_options)?;
+        dataframes.push((path.as_path(), df));
+    }
 
-    for entry in fs::read_dir(base_dir)? {
-        let path = entry?.path();
-        if path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("parquet") {
-            let df = ParquetReader::new(File::open(path)?).finish()?;
-            dataframes.push(df);
+    if dataframes.is_empty() {
+        return Err(ExtractError::NoFilesFound(pattern.to_string()));
+    }
+    concat_with_partitions(dataframes)
+}
+
+fn is_glob_pattern(path: &str) -> bool {
+    path.contains(['*', '?', '['])
+}
+
+/// Recursively collects `.parquet` files in a stable order, skipping hidden
+/// and metadata entries such as `_SUCCESS` or `.crc` files.
+fn collect_parquet_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), ExtractError> {
+    let mut entries = fs::read_dir(dir)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    entries.sort();
+
+    for path in entries {
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+        if name.starts_with('.') || name.starts_with('_') {
+            continue;
+        }
+        if path.is_dir() {
+            collect_parquet_files(&path, files)?;
+        } else if path.extension().and_then(|s| s.to_str()) == Some("parquet") {
+            files.push(path);
         }
     }
+    Ok(())
+}
+
+/// Adds partition columns parsed from the directories in each file's path,
+/// including those above a glob's first wildcard, and concatenates the results.
+fn concat_with_partitions(dataframes: Vec<(&Path, DataFrame)>) -> Result<DataFrame, ExtractError> {
+    let partitions: Vec<Vec<(String, Option<String>)>> =
+        dataframes.iter().map(|(path, _)| hive_partitions(path)).collect();
 
-    dataframes
+    let mut keys: Vec<&str> = Vec::new();
+    for (key, _) in partitions.iter().flatten() {
+        if !keys.contains(&key.as_str()) {
+            keys.push(key);
+        }
+    }
+    let integer_keys: Vec<bool> = keys
+        .iter()
+        .map(|key| {
+            partitions
+                .iter()
+                .flatten()
+                .filter(|(k, _)| k == key)
+                .all(|(_, v)| v.as_deref().is_none_or(|v| v.parse::<i64>().is_ok()))
+        })
+        .collect();
+
+    let dataframes = dataframes
+        .into_iter()
+        .zip(&partitions)
+        .map(|((_, mut df), values)| {
+            for (key, integer) in keys.iter().zip(&integer_keys) {
+                if df.get_column_index(key).is_some() {
+                    continue;
+                }
+                let value = values.iter().find(|(k, _)| k == key).and_then(|(_, v)| v.as_deref());
+                let series = if *integer {
+                    Series::new(key, vec![value.map(|v| v.parse::<i64>().unwrap_or_default()); df.height()])
+                } else {
+                    Series::new(key, vec![value; df.height()])
+                };
+                df.with_column(series)?;
+            }
+            Ok(df)
+        })
+        .collect::<Result<Vec<_>, ExtractError>>()?;
+
+    concat_unified(dataframes)
+}
+
+fn hive_partitions(path: &Path) -> Vec<(String, Option<String>)> {
+    path.parent()
         .into_iter()
-        .reduce(|acc, df| acc.vstack(&df).unwrap())
-        .ok_or_else(|| ExtractError::Io(std::io::Error::new(std::io::ErrorKind::NotFound, "No dataframes found")))
+        .flat_map(|dir| dir.components())
+        .filter_map(|c| {
+            let component = c.as_os_str().to_str()?;
+            let (key, value) = component.split_once('=')?;
+            let value = unescape_hive_value(value);
+            Some((key.to_string(), (value != "__HIVE_DEFAULT_PARTITION__").then_some(value)))
+        })
+        .collect()
+}
+
+fn unescape_hive_value(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .filter(|h| h.iter().all(u8::is_ascii_hexdigit))
+            .and_then(|h| std::str::from_utf8(h).ok());
+        match (bytes[i], hex.and_then(|h| u8::from_str_radix(h, 16).ok())) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
 }
 
-fn read_json_file(file_path: &str) -> Result<DataFrame, ExtractError> {
-    let file = File::open(file_path)?;
-    Ok(JsonReader::new(file).finish()?)
+/// Stacks dataframes whose schemas may differ in column order, missing
+/// columns or numeric width. Columns are cast to their common supertype and
+/// missing columns are filled with nulls.
+fn concat_unified(dataframes: Vec<DataFrame>) -> Result<DataFrame, ExtractError> {
+    let mut fields: Vec<(String, DataType)> = Vec::new();
+    for df in &dataframes {
+        for series in df.get_columns() {
+            match fields.iter_mut().find(|(name, _)| name == series.name()) {
+                Some((name, dtype)) => {
+                    *dtype = try_get_supertype(dtype, series.dtype()).map_err(|_| {
+                        ExtractError::SchemaMismatch(format!(
+                            "column '{}' has incompatible types {} and {}",
+                            name,
+                            dtype,
+                            series.dtype()
+                        ))
+                    })?;
+                }
+                None => fields.push((series.name().to_string(), series.dtype().clone())),
+            }
+        }
+    }
+
+    let mut result: Option<DataFrame> = None;
+    for df in dataframes {
+        let columns = fields
+            .iter()
+            .map(|(name, dtype)| match df.column(name) {
+                Ok(series) => Ok(series.cast(dtype)?),
+                Err(_) => Ok(Series::full_null(name, df.height(), dtype)),
+            })
+            .collect::<Result<Vec<_>, ExtractError>>()?;
+        let df = DataFrame::new(columns)?;
+        match result.as_mut() {
+            Some(acc) => {
+                acc.vstack_mut(&df)?;
+            }
+            None => result = Some(df),
+        }
+    }
+
+    let mut df = result.unwrap_or_default();
+    df.as_single_chunk_par();
+    Ok(df)
+}
+
+fn read_ipc_stream<R: Read>(reader: R) -> Result<DataFrame, ExtractError> {
+    Ok(IpcStreamReader::new(reader).finish()?)
 }
 
-fn read_csv_file(file_path: &str) -> Result<DataFrame, ExtractError> {
-    let file = File::open(file_path)?;
-    Ok(CsvReader::new(file).finish()?)
+fn read_json<R: MmapBytesReader>(reader: R, format: JsonFormat) -> Result<DataFrame, ExtractError> {
+    Ok(JsonReader::new(reader).with_json_format(format).finish()?)
 }
 
-fn write_dataframe_to_parquet(df: &mut DataFrame, file_path: &str) -> Result<(), ExtractError> {
-    let file = File::create(file_path)?;
+fn read_csv<R: MmapBytesReader>(reader: R, options: &CsvOptions, default_delimiter: char) -> Result<DataFrame, ExtractError> {
+    let null_values = (!options.null_value.is_empty())
+        .then(|| NullValues::AllColumnsSingle(options.null_value.clone()));
+    let parse_options = CsvParseOptions::default()
+        .with_separator(options.separator(default_delimiter)?)
+        .with_quote_char(Some(options.quote_byte()?))
+        .with_eol_char(options.eol_byte()?)
+        .with_null_values(null_values);
+    Ok(CsvReadOptions::default()
+        .with_has_header(options.header)
+        .with_parse_options(parse_options)
+        .into_reader_with_file_handle(reader)
+        .finish()?)
+}
+
+fn write_dataframe_to_parquet(df: &mut DataFrame, file_path: &str, options: &ParquetOptions) -> Result<(), ExtractError> {
+    if !options.partition_by.is_empty() || options.max_rows_per_file.is_some() {
+        return write_partitioned_parquet(df, file_path, options);
+    }
+    write_single_parquet_file(df, Path::new(file_path), options)
+}
+
+fn write_single_parquet_file(df: &mut DataFrame, path: &Path, options: &ParquetOptions) -> Result<(), ExtractError> {
+    let compression = options.compression()?;
+    let file = File::create(path)?;
     let writer = BufWriter::new(file);
-    ParquetWriter::new(writer).finish(df)?;
+    if !options.dictionary {
+        return write_parquet_plain(df, writer, compression, options);
+    }
+    ParquetWriter::new(writer)
+        .with_compression(compression)
+        .with_statistics(options.statistics)
+        .with_row_group_size(options.row_group_size)
+        .with_data_page_size(options.data_page_size)
+        .finish(df)?;
     Ok(())
 }
 
-fn write_dataframe_to_json(df: &mut DataFrame, file_path: &str) -> Result<(), ExtractError> {
-    let mut file = File::create(file_path)?;
-    JsonWriter::new(&mut file)
-        .with_json_format(JsonFormat::Json)
-        .finish(df)?;
+/// Writes `df` as a directory of `part-N.parquet` files, nested under
+/// Hive-style `column=value` directories for each `partition_by` column.
+fn write_partitioned_parquet(df: &mut DataFrame, base_dir: &str, options: &ParquetOptions) -> Result<(), ExtractError> {
+    if options.max_rows_per_file == Some(0) {
+        return Err(ExtractError::InvalidOption("'max_rows_per_file' must be greater than 0".to_string()));
+    }
+    if !options.partition_by.is_empty() && options.partition_by.len() >= df.width() {
+        return Err(ExtractError::InvalidOption("Cannot partition by every column".to_string()));
+    }
+
+    let partitions = if options.partition_by.is_empty() {
+        vec![(Path::new(base_dir).to_path_buf(), df.clone())]
+    } else {
+        df.partition_by_stable(&options.partition_by, true)?
+            .into_iter()
+            .map(|partition| {
+                let mut dir = Path::new(base_dir).to_path_buf();
+                for key in &options.partition_by {
+                    dir.push(format!("{}={}", key, hive_partition_value(partition.column(key)?.get(0)?)));
+                }
+                Ok((dir, partition.drop_many(&options.partition_by)))
+            })
+            .collect::<Result<Vec<_>, ExtractError>>()?
+    };
+
+    for (dir, partition) in partitions {
+        fs::create_dir_all(&dir)?;
+        let rows_per_file = options.max_rows_per_file.unwrap_or(partition.height()).max(1);
+        for (part, offset) in (0..partition.height().max(1)).step_by(rows_per_file).enumerate() {
+            let mut chunk = partition.slice(offset as i64, rows_per_file);
+            write_single_parquet_file(&mut chunk, &dir.join(format!("part-{}.parquet", part)), options)?;
+        }
+    }
+    Ok(())
+}
+
+/// Formats a partition key for use in a directory name, escaping characters
+/// that are not allowed in paths the same way Hive does.
+fn hive_partition_value(value: AnyValue) -> String {
+    let raw = match value {
+        AnyValue::Null => return "__HIVE_DEFAULT_PARTITION__".to_string(),
+        AnyValue::String(s) => s.to_string(),
+        other => other.to_string(),
+    };
+    raw.chars()
+        .map(|c| match c {
+            '"' | '#' | '%' | '\'' | '*' | '/' | ':' | '=' | '?' | '\\' | '{' | '[' | ']' | '^' => {
+                format!("%{:02X}", c as u32)
+            }
+            c if c.is_control() => format!("%{:02X}", c as u32),
+            c => c.to_string(),
+        })
+        .collect()
+}
+
+/// `ParquetWriter` always dictionary-encodes strings and integers, so writing
+/// without dictionaries goes through the lower level `polars_parquet` writer
+/// with plain encoding for every column.
+fn write_parquet_plain<W: Write>(
+    df: &mut DataFrame,
+    writer: W,
+    compression: ParquetCompression,
+    options: &ParquetOptions,
+) -> Result<(), ExtractError> {
+    let write_options = WriteOptions {
+        write_statistics: options.statistics,
+        compression: compression.into(),
+        version: Version::V1,
+        data_pagesize_limit: options.data_page_size,
+    };
+    let schema = df.schema().to_arrow(true);
+    let parquet_schema = to_parquet_schema(&schema)?;
+    let encodings: Vec<Vec<Encoding>> = schema
+        .fields
+        .iter()
+        .map(|field| transverse(&field.data_type, |_| Encoding::Plain))
+        .collect();
+
+    let row_group_size = options.row_group_size.unwrap_or(512 * 512).max(1);
+    let mut file_writer = FileWriter::try_new(writer, schema, write_options)?;
+    for offset in (0..df.height()).step_by(row_group_size) {
+        let mut row_group = df.slice(offset as i64, row_group_size);
+        row_group.as_single_chunk_par();
+        for batch in row_group.iter_chunks(true) {
+            file_writer.write(row_group_iter(
+                batch,
+                encodings.clone(),
+                parquet_schema.fields().to_vec(),
+                write_options,
+            ))?;
+        }
+    }
+    file_writer.end(None)?;
+    Ok(())
+}
+
+fn write_dataframe_to_json<W: Write>(df: &mut DataFrame, writer: W, format: JsonFormat) -> Result<(), ExtractError> {
+    JsonWriter::new(writer)
+        .with_json_format(format)
+        .finish(&mut cast_decimals(df, &DataType::Float64)?)?;
+    Ok(())
+}
+
+/// Casts decimal columns, and decimals nested in lists and structs, to `to`,
+/// since polars can't write decimals to CSV or JSON.
+fn cast_decimals(df: &DataFrame, to: &DataType) -> PolarsResult<DataFrame> {
+    fn replace(dtype: &DataType, to: &DataType) -> DataType {
+        match dtype {
+            DataType::Decimal(..) => to.clone(),
+            DataType::List(inner) => DataType::List(Box::new(replace(inner, to))),
+            DataType::Struct(fields) => DataType::Struct(
+                fields.iter().map(|field| Field::new(field.name(), replace(field.data_type(), to))).collect(),
+            ),
+            other => other.clone(),
+        }
+    }
+    df.get_columns()
+        .iter()
+        .map(|series| {
+            let dtype = replace(series.dtype(), to);
+            if &dtype == series.dtype() {
+                Ok(series.clone())
+            } else {
+                series.cast(&dtype)
+            }
+        })
+        .collect()
+}
+
+fn write_dataframe_to_ipc_stream<W: Write>(df: &mut DataFrame, writer: W) -> Result<(), ExtractError> {
+    IpcStreamWriter::new(writer).finish(df)?;
+    Ok(())
+}
+
+fn write_dataframe_to_csv<W: Write>(
+    df: &mut DataFrame,
+    writer: W,
+    options: &CsvOptions,
+    default_delimiter: char,
+) -> Result<(), ExtractError> {
+    CsvWriter::new(writer)
+        .include_bom(options.bom)
+        .include_header(options.header)
+        .with_separator(options.separator(default_delimiter)?)
+        .with_quote_char(options.quote_byte()?)
+        .with_quote_style(options.quote_style.into())
+        .with_null_value(options.null_value.clone())
+        .with_line_terminator(options.line_terminator.clone())
+        .with_date_format(options.date_format.clone())
+        .with_time_format(options.time_format.clone())
+        .with_datetime_format(options.datetime_format.clone())
+        .finish(&mut cast_decimals(df, &DataType::String)?)?;
     Ok(())
 }
 
-fn write_dataframe_to_csv(df: &mut DataFrame, file_path: &str) -> Result<(), ExtractError> {
-    let mut file = File::create(file_path)?;
-    CsvWriter::new(&mut file).finish(df)?;
+fn write_dataframe_to_xlsx(df: &mut DataFrame, file_path: &str) -> Result<(), ExtractError> {
+    df.as_single_chunk_par();
+
+    let date_format = Format::new().set_num_format("yyyy-mm-dd");
+    let datetime_format = Format::new().set_num_format("yyyy-mm-dd hh:mm:ss");
+    let time_format = Format::new().set_num_format("hh:mm:ss");
+    let formats = [&date_format, &datetime_format, &time_format];
+
+    let rows_per_sheet = XLSX_MAX_ROWS - 1;
+    let sheet_count = df.height().div_ceil(rows_per_sheet).max(1);
+
+    let mut workbook = Workbook::new();
+    for sheet in 0..sheet_count {
+        let worksheet = workbook.add_worksheet_with_constant_memory();
+        worksheet.set_name(format!("Sheet{}", sheet + 1))?;
+
+        for (col, name) in df.get_column_names().iter().enumerate() {
+            worksheet.write_string(0, col as u16, *name)?;
+        }
+
+        let start = sheet * rows_per_sheet;
+        let end = (start + rows_per_sheet).min(df.height());
+        for row in start..end {
+            let sheet_row = (row - start + 1) as u32;
+            for (col, series) in df.get_columns().iter().enumerate() {
+                write_xlsx_cell(worksheet, sheet_row, col as u16, series.get(row)?, &formats)?;
+            }
+        }
+    }
+
+    workbook.save(file_path)?;
     Ok(())
-}
\ No newline at end of file
+}
+
+fn write_xlsx_cell(
+    worksheet: &mut Worksheet,
+    row: u32,
+    col: u16,
+    value: AnyValue,
+    [date_format, datetime_format, time_format]: &[&Format; 3],
+) -> Result<(), ExtractError> {
+    match value {
+        AnyValue::Null => {}
+        AnyValue::Boolean(v) => {
+            worksheet.write_boolean(row, col, v)?;
+        }
+        AnyValue::UInt8(v) => {
+            worksheet.write_number(row, col, v)?;
+        }
+        AnyValue::UInt16(v) => {
+            worksheet.write_number(row, col, v)?;
+        }
+        AnyValue::UInt32(v) => {
+            worksheet.write_number(row, col, v)?;
+        }
+        AnyValue::UInt64(v) => {
+            worksheet.write_number(row, col, v as f64)?;
+        }
+        AnyValue::Int8(v) => {
+            worksheet.write_number(row, col, v)?;
+        }
+        AnyValue::Int16(v) => {
+            worksheet.write_number(row, col, v)?;
+        }
+        AnyValue::Int32(v) => {
+            worksheet.write_number(row, col, v)?;
+        }
+        AnyValue::Int64(v) => {
+            worksheet.write_number(row, col, v as f64)?;
+        }
+        AnyValue::Float32(v) => {
+            worksheet.write_number(row, col, v)?;
+        }
+        AnyValue::Float64(v) => {
+            worksheet.write_number(row, col, v)?;
+        }
+        AnyValue::Decimal(v, scale) => {
+            worksheet.write_number(row, col, v as f64 / 10f64.powi(scale as i32))?;
+        }
+        AnyValue::Date(_) | AnyValue::Datetime(..) | AnyValue::Time(_) => {
+            let (serial, format) = match value {
+                AnyValue::Date(days) => (days as f64 + XLSX_UNIX_EPOCH_OFFSET, date_format),
+                AnyValue::Datetime(v, unit, _) => {
+                    let seconds = match unit {
+                        TimeUnit::Nanoseconds => v as f64 / 1e9,
+                        TimeUnit::Microseconds => v as f64 / 1e6,
+                        TimeUnit::Milliseconds => v as f64 / 1e3,
+                    };
+                    (seconds / 86_400.0 + XLSX_UNIX_EPOCH_OFFSET, datetime_format)
+                }
+                AnyValue::Time(nanos) => (nanos as f64 / 86_400e9, time_format),
+                _ => unreachable!("matched a temporal value above"),
+            };
+            // Excel dates only run from 1900 to 9999; anything else is written as text.
+            match ExcelDateTime::from_serial_datetime(serial) {
+                Ok(datetime) => worksheet.write_datetime_with_format(row, col, &datetime, format)?,
+                Err(_) => worksheet.write_string(row, col, value.to_string())?,
+            };
+        }
+        AnyValue::String(v) => {
+            worksheet.write_string(row, col, v)?;
+        }
+        other => {
+            worksheet.write_string(row, col, other.to_string())?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unescape_hive_values() {
+        assert_eq!(unescape_hive_value("plain"), "plain");
+        assert_eq!(unescape_hive_value("a%2Fb%3Dc"), "a/b=c");
+        assert_eq!(unescape_hive_value("%3a%3A"), "::");
+        assert_eq!(unescape_hive_value("caf%C3%A9"), "café");
+        assert_eq!(unescape_hive_value("100%"), "100%");
+        assert_eq!(unescape_hive_value("%2"), "%2");
+        assert_eq!(unescape_hive_value("%zz%+1%-1"), "%zz%+1%-1");
+        assert_eq!(unescape_hive_value("%252F"), "%2F");
+    }
+
+    #[test]
+    fn escaped_partition_values_round_trip() {
+        for value in ["a/b", "x=y", "50%", "what?", "[1]", "tab\tend", "ünï"] {
+            let escaped = hive_partition_value(AnyValue::String(value));
+            assert!(!escaped.contains(['/', '=']));
+            assert_eq!(unescape_hive_value(&escaped), value);
+        }
+    }
+
+    #[test]
+    fn partitions_come_from_every_directory() {
+        let partitions = hive_partitions(Path::new("/data/year=2024/sub/city=S%C3%A3o%20Paulo/region=__HIVE_DEFAULT_PARTITION__/part=1.parquet"));
+        assert_eq!(
+            partitions,
+            [
+                ("year".to_string(), Some("2024".to_string())),
+                ("city".to_string(), Some("São Paulo".to_string())),
+                ("region".to_string(), None),
+            ]
+        );
+        assert!(hive_partitions(Path::new("file.parquet")).is_empty());
+    }
+
+    #[test]
+    fn partition_columns_are_typed_and_filled() {
+        let a = df!("v" => [1i32, 2]).unwrap();
+        let b = df!("v" => [3i32]).unwrap();
+        let c = df!("v" => [4i32], "city" => ["kept"]).unwrap();
+        let df = concat_with_partitions(vec![
+            (Path::new("t/year=2023/city=Oslo/a.parquet"), a),
+            (Path::new("t/year=__HIVE_DEFAULT_PARTITION__/city=Rome/b.parquet"), b),
+            (Path::new("t/year=2024/city=Lima/c.parquet"), c),
+        ])
+        .unwrap();
+        assert_eq!(df.column("year").unwrap().dtype(), &DataType::Int64);
+        let years: Vec<Option<i64>> = df.column("year").unwrap().i64().unwrap().into_iter().collect();
+        assert_eq!(years, [Some(2023), Some(2023), None, Some(2024)]);
+        let cities: Vec<Option<&str>> = df.column("city").unwrap().str().unwrap().into_iter().collect();
+        assert_eq!(cities, [Some("Oslo"), Some("Oslo"), Some("Rome"), Some("kept")]);
+    }
+
+    #[test]
+    fn compression_must_match_the_extension() {
+        let mut df = df!("n" => [1i64, 2]).unwrap();
+        let options = OutputOptions { compression: Some(Compression::Zstd), ..OutputOptions::default() };
+        let path = std::env::temp_dir().join("rsfake-test-mismatch.csv.gz");
+        let _ = fs::remove_file(&path);
+        let path = path.to_str().unwrap();
+        let error = write_dataframe(&mut df, path, "csv", &options).unwrap_err();
+        assert!(matches!(error, ExtractError::FormatMismatch(_)), "{}", error);
+        assert!(!Path::new(path).exists());
+
+        let options = OutputOptions { compression: Some(Compression::Gzip), ..OutputOptions::default() };
+        write_dataframe(&mut df, path, "csv", &options).unwrap();
+        assert!(read_file(path, &CsvOptions::default()).unwrap().equals(&df));
+    }
+
+    #[test]
+    fn outputs_are_checked_before_writing() {
+        let defaults = OutputOptions::default();
+        let gzip = OutputOptions { compression: Some(Compression::Gzip), ..OutputOptions::default() };
+        assert!(matches!(check_output("-", "xlsx", &defaults), Err(ExtractError::InvalidOption(_))));
+        assert!(matches!(check_output("out.parquet", "parquet", &gzip), Err(ExtractError::InvalidOption(_))));
+        assert!(matches!(check_output("out.parquet.gz", "parquet", &defaults), Err(ExtractError::InvalidOption(_))));
+        assert!(matches!(check_output("out.txt", "yaml", &defaults), Err(ExtractError::UnsupportedFormat(_))));
+        assert_eq!(check_output("-", "jsonl", &gzip).unwrap(), Some(Compression::Gzip));
+        assert_eq!(check_output("out.csv.xz", "csv", &defaults).unwrap(), Some(Compression::Xz));
+        assert_eq!(check_output("out.csv", "csv", &defaults).unwrap(), None);
+    }
+
+    #[test]
+    fn glob_characters() {
+        assert!(is_glob_pattern("data/*.parquet"));
+        assert!(is_glob_pattern("data/file?.csv"));
+        assert!(is_glob_pattern("data/[ab].csv"));
+        assert!(!is_glob_pattern("data/file.csv"));
+    }
+}
@@ -1,19 +1,15 @@
-use fake::Fake;
-use fake::faker::company::raw::{Profession, Industry};
-#[cfg(feature="rust_decimal")]
-use fake::decimal::{Decimal, PositiveDecimal, NegativeDecimal, NoDecimalPoints};
-#[cfg(feature="bigdecimal")]
-use fake::bigdecimal::{BigDecimal, PositiveBigDecimal, NegativeBigDecimal, NoBigDecimalPoints};
+use polars::chunked_array::builder::get_list_builder;
 use polars::prelude::*;
-use rand::Rng;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use rayon::prelude::*;
 use serde_json::Value;
-use std::fs;
-use std::str::FromStr;
+use std::borrow::Cow;
+use std::sync::Arc;
 use thiserror::Error;
 
-#[cfg(feature = "chrono")]
-use chrono::{DateTime, Duration, Utc};
+use crate::registry::{self, ColumnGenerator, Registry};
+use crate::schema::Schema;
 
 #[derive(Error, Debug)]
 pub enum GenerateError {
@@ -21,6 +17,12 @@ pub enum GenerateError {
     Io(#[from] std::io::Error),
     #[error("JSON error: {0}")]
     Json(#[from] serde_json::Error),
+    #[error("YAML error: {0}")]
+    Yaml(#[from] serde_yaml::Error),
+    #[error("TOML error: {0}")]
+    Toml(#[from] toml::de::Error),
+    #[error("TOML error: {0}")]
+    TomlSerialize(#[from] toml::ser::Error),
     #[error("Polars error: {0}")]
     Polars(#[from] polars::error::PolarsError),
     #[error("Unsupported type: {0}")]
@@ -29,346 +31,452 @@ pub enum GenerateError {
     InvalidArgument(String),
 }
 
-pub fn generate_from_json(json_file: &str, no_rows: usize) -> Result<DataFrame, GenerateError> {
-    let json: Value = serde_json::from_str(&fs::read_to_string(json_file)?)?;
-
-    let columns = json
-        .get("columns")
-        .and_then(|c| c.as_array())
-        .ok_or_else(|| GenerateError::InvalidArgument("Missing or invalid 'columns' array in JSON schema".to_string()))?
-        .par_iter()
-        .map(|col_def| {
-            let col_name = col_def
-                .get("name")
-                .and_then(|n| n.as_str())
-                .unwrap_or_default();
-            let col_type = col_def
-                .get("type")
-                .and_then(|t| t.as_str())
-                .unwrap_or_default();
-
-            create_series_from_type(col_type, col_name, no_rows, col_def)
-        })
-        .collect::<Result<Vec<Series>, GenerateError>>()?;
-
-    Ok(DataFrame::new(columns)?)
+/// Rows generated when [`Generator::rows`] isn't called.
+pub const DEFAULT_ROWS: usize = 10_000;
+
+/// Generates a [`DataFrame`] from a [`Schema`].
+///
+/// ```
+/// use rsfake::{Generator, Schema};
+///
+/// let schema = Schema::from_json(r#"{ "columns": [{ "name": "name", "type": "Name" }] }"#)?;
+/// let df = Generator::new(schema).rows(100).generate()?;
+/// assert_eq!(df.shape(), (100, 1));
+/// # Ok::<(), rsfake::GenerateError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Generator {
+    schema: Schema,
+    rows: usize,
+    seed: Option<u64>,
+    registry: Arc<Registry>,
 }
 
-fn create_series_from_type(
-    type_name: &str,
-    col_name: &str,
-    no_rows: usize,
-    col_def: &Value,
-) -> Result<Series, GenerateError> {
-    use fake::faker::*;
-    use fake::locales::EN;
+impl Generator {
+    /// Creates a generator for `schema` producing [`DEFAULT_ROWS`] random rows.
+    pub fn new(schema: Schema) -> Self {
+        Generator { schema, rows: DEFAULT_ROWS, seed: None, registry: registry::builtins().clone() }
+    }
 
-    macro_rules! generate_series {
-        ($faker:expr) => {{
-            let data: Vec<String> = (0..no_rows)
-                .into_par_iter()
-                .map(|_| $faker.fake())
-                .collect();
-            Series::new(col_name, data)
-        }};
+    /// Resolves column types through `registry` instead of the built-in
+    /// types, so schemas can use custom [`ColumnGenerator`]s.
+    pub fn registry(mut self, registry: Registry) -> Self {
+        self.registry = Arc::new(registry);
+        self
     }
 
-    macro_rules! generate_duration_series {
-        ($faker_type:expr) => {{
-            let data: Vec<String> = (0..no_rows)
-                .into_par_iter()
-                .map(|_| {
-                    let duration: Duration = $faker_type.fake();
-                    duration.to_string()
-                })
-                .collect();
-            Series::new(col_name, data)
-        }};
+    /// Makes the output reproducible: the same schema, seed and row count
+    /// always generate the same data, whatever the number of threads.
+    ///
+    /// ```
+    /// use rsfake::{Generator, Schema};
+    ///
+    /// let schema = Schema::from_json(r#"{ "columns": [{ "name": "email", "type": "SafeEmail" }] }"#)?;
+    /// let a = Generator::new(schema.clone()).seed(42).rows(10).generate()?;
+    /// let b = Generator::new(schema).seed(42).rows(10).generate()?;
+    /// assert!(a.equals(&b));
+    /// # Ok::<(), rsfake::GenerateError>(())
+    /// ```
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
     }
 
-    macro_rules! generate_range_series {
-        ($type:ty, $default_start:expr, $default_end:expr) => {{
-            let (start, end) = get_range_args::<$type>(col_def, $default_start, $default_end)?;
-            let data: Vec<$type> = (0..no_rows)
-                .into_par_iter()
-                .map(|_| rand::thread_rng().gen_range(start..=end))
-                .collect();
-            Series::new(col_name, data)
-        }};
+    /// Sets the number of rows to generate.
+    ///
+    /// ```
+    /// use rsfake::{Generator, Schema};
+    ///
+    /// let schema = Schema::from_json(r#"{ "columns": [{ "name": "n", "type": "u32" }] }"#)?;
+    /// assert_eq!(Generator::new(schema).rows(5).generate()?.height(), 5);
+    /// # Ok::<(), rsfake::GenerateError>(())
+    /// ```
+    pub fn rows(mut self, rows: usize) -> Self {
+        self.rows = rows;
+        self
     }
-    
-    macro_rules! generate_datetime_series {
-        ($faker_type:expr) => {{
-            let data: Vec<String> = (0..no_rows)
-                .into_par_iter()
-                .map(|_| $faker_type.fake::<DateTime<Utc>>().to_rfc3339())
-                .collect();
-            Series::new(col_name, data)
-        }};
+
+    /// Generates all rows at once.
+    ///
+    /// ```
+    /// use rsfake::{Generator, Schema};
+    ///
+    /// let schema = Schema::from_path("examples/schema-numeric.json")?;
+    /// let df = Generator::new(schema).rows(1_000).generate()?;
+    /// assert_eq!(df.height(), 1_000);
+    /// # Ok::<(), rsfake::GenerateError>(())
+    /// ```
+    pub fn generate(&self) -> Result<DataFrame, GenerateError> {
+        self.generate_rows(self.rows, 0, self.seed)
     }
 
-    macro_rules! generate_uuid_series {
-        ($faker_type:expr) => {{
-            let data: Vec<String> = (0..no_rows)
-                .into_par_iter()
-                .map(|_| $faker_type.fake::<uuid::Uuid>().to_string())
-                .collect();
-            Series::new(col_name, data)
-        }};
+    /// Generates the rows in batches of at most `batch_size`, so large
+    /// datasets can be streamed without holding them in memory. With a seed,
+    /// each batch is reproducible, though the batches differ from
+    /// [`Generator::generate`] with the same seed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `batch_size` is zero.
+    ///
+    /// ```
+    /// use rsfake::{Generator, Schema};
+    ///
+    /// let schema = Schema::from_json(r#"{ "columns": [{ "name": "word", "type": "Word" }] }"#)?;
+    /// let heights = Generator::new(schema)
+    ///     .rows(250)
+    ///     .batches(100)
+    ///     .map(|batch| batch.map(|df| df.height()))
+    ///     .collect::<Result<Vec<_>, _>>()?;
+    /// assert_eq!(heights, [100, 100, 50]);
+    /// # Ok::<(), rsfake::GenerateError>(())
+    /// ```
+    pub fn batches(&self, batch_size: usize) -> Batches<'_> {
+        assert!(batch_size > 0, "batch_size must be greater than zero");
+        Batches { generator: self, batch_size, next_row: 0, index: 0 }
     }
 
-    macro_rules! generate_decimal_series {
-        ($faker_type:expr, $decimal_type:ty) => {{
-            let data: Vec<String> = (0..no_rows)
-                .into_par_iter()
-                .map(|_| $faker_type.fake::<$decimal_type>().to_string())
-                .collect();
-            Series::new(col_name, data)
-        }};
+    fn generate_rows(&self, rows: usize, first_row: usize, seed: Option<u64>) -> Result<DataFrame, GenerateError> {
+        let columns = self
+            .schema
+            .columns()
+            .par_iter()
+            .enumerate()
+            .map(|(index, col_def)| {
+                let column_seed = seed.map_or_else(|| rand::thread_rng().gen(), |seed| derive_seed(seed, index as u64));
+                self.generate_column(col_def, column_seed, rows, first_row)
+            })
+            .collect::<Result<Vec<Series>, GenerateError>>()?;
+
+        Ok(DataFrame::new(columns)?)
     }
 
-    fn generate_boolean_series(ratio: u8, no_rows: usize, col_name: &str) -> Series {
-        let data: Vec<bool> = (0..no_rows)
-            .into_par_iter()
-            .map(|_| boolean::raw::Boolean(EN, ratio).fake())
-            .collect();
-        Series::new(col_name, data)
+    fn generate_column(
+        &self,
+        col_def: &Value,
+        column_seed: u64,
+        rows: usize,
+        first_row: usize,
+    ) -> Result<Series, GenerateError> {
+        let col_name = col_def
+            .get("name")
+            .and_then(|n| n.as_str())
+            .unwrap_or_default();
+        let col_type = col_def
+            .get("type")
+            .and_then(|t| t.as_str())
+            .unwrap_or_default();
+
+        // Values and nulls draw from separate streams so null_ratio doesn't shift the values.
+        let series = match col_type {
+            "" | "auto" => {
+                return Err(GenerateError::InvalidArgument(format!(
+                    "Column '{}' has no type; set one, or pick one from the name with --auto-types",
+                    col_name
+                )))
+            }
+            // Struct columns have no validity of their own; a null row would only null the fields.
+            "Object" if col_def.get("null_ratio").is_some() => {
+                return Err(GenerateError::InvalidArgument(format!(
+                    "'null_ratio' isn't supported on Object column '{}'; set it on the fields instead",
+                    col_name
+                )))
+            }
+            "Object" => self.generate_object(col_name, col_def, column_seed, rows, first_row)?,
+            "Array" => self.generate_array(col_name, col_def, column_seed, rows)?,
+            _ => {
+                let values = |seed, rows| {
+                    let series =
+                        self.registry.create_series(col_type, col_name, &RowSeeds::Seeded { seed, rows, first_row }, col_def)?;
+                    apply_max_length(series, col_def)
+                };
+                let series = values(column_seed, rows)?;
+                if col_def.get("unique").and_then(Value::as_bool).unwrap_or(false) {
+                    make_unique(series, rows, |attempt, rows| values(derive_seed(column_seed, u64::MAX - 1 - attempt), rows))?
+                } else {
+                    series
+                }
+            }
+        };
+        let nulls = RowSeeds::Seeded { seed: derive_seed(column_seed, u64::MAX), rows, first_row };
+        apply_null_ratio(series, col_def, &nulls)
     }
 
-    Ok(match type_name {
-        "u32" => generate_range_series!(u32, u32::MIN, u32::MAX),
-        "u64" => generate_range_series!(u64, u64::MIN, u64::MAX),
-        "i32" => generate_range_series!(i32, i32::MIN, i32::MAX),
-        "i64" => generate_range_series!(i64, i64::MIN, i64::MAX),
-        "f32" => generate_range_series!(f32, 0.0, 1.0),
-        "f64" => generate_range_series!(f64, 0.0, 1.0),
-        "Boolean" => {
-            let ratio = get_args_u8(col_def, "ratio")?;
-            generate_boolean_series(ratio, no_rows, col_name)
-        },
-        "Word" => generate_series!(lorem::raw::Word(EN)),
-        "Sentence" => {
-            let (start, end) = get_range_args(col_def, 3, 10)?;
-            generate_series!(lorem::raw::Sentence(EN, start..end))
-        }
-        "Paragraph" => {
-            let (start, end) = get_range_args(col_def, 3, 7)?;
-            generate_series!(lorem::raw::Paragraph(EN, start..end))
+    /// A struct column with one field per entry in `columns`.
+    fn generate_object(
+        &self,
+        col_name: &str,
+        col_def: &Value,
+        seed: u64,
+        rows: usize,
+        first_row: usize,
+    ) -> Result<Series, GenerateError> {
+        let fields = col_def
+            .get("columns")
+            .and_then(Value::as_array)
+            .filter(|fields| !fields.is_empty())
+            .ok_or_else(|| GenerateError::InvalidArgument(format!("Object column '{}' needs a non-empty 'columns' list", col_name)))?
+            .iter()
+            .enumerate()
+            .map(|(index, field)| self.generate_column(field, derive_seed(seed, index as u64), rows, first_row))
+            .collect::<Result<Vec<Series>, GenerateError>>()?;
+        Ok(StructChunked::new(col_name, &fields)?.into_series())
+    }
+
+    /// A list column of `items` values, with `min_items..=max_items` per row.
+    fn generate_array(&self, col_name: &str, col_def: &Value, seed: u64, rows: usize) -> Result<Series, GenerateError> {
+        let items = col_def
+            .get("items")
+            .filter(|items| items.is_object())
+            .ok_or_else(|| GenerateError::InvalidArgument(format!("Array column '{}' needs an 'items' column", col_name)))?;
+        let args = col_def.get("args");
+        let bound = |key, default| {
+            args.and_then(|a| a.get(key)).map_or(Ok(default), |v| {
+                v.as_u64()
+                    .map(|v| v as usize)
+                    .ok_or_else(|| GenerateError::InvalidArgument(format!("'{}' must be a non-negative integer", key)))
+            })
+        };
+        let min_items = bound("min_items", 1)?;
+        let max_items = bound("max_items", min_items.max(3))?;
+        if min_items > max_items {
+            return Err(GenerateError::InvalidArgument("'min_items' must be less than or equal to 'max_items'".to_string()));
         }
-        "FirstName" => generate_series!(name::raw::FirstName(EN)),
-        "LastName" => generate_series!(name::raw::LastName(EN)),
-        "Title" => generate_series!(name::raw::Title(EN)),
-        "Suffix" => generate_series!(name::raw::Suffix(EN)),
-        "Name" => generate_series!(name::raw::Name(EN)),
-        "NameWithTitle" => generate_series!(name::raw::NameWithTitle(EN)),
-        "Seniority" => generate_series!(job::raw::Seniority(EN)),
-        "Field" => generate_series!(job::raw::Field(EN)),
-        "Position" => generate_series!(job::raw::Position(EN)),
-        "JobTitle" => generate_series!(job::raw::Title(EN)),
-        "Digit" => generate_series!(number::raw::Digit(EN)),
-        "NumberWithFormat" => {
-            let fmt = get_args_string(col_def, "fmt")?;
-            generate_series!(number::raw::NumberWithFormat(EN, &fmt))
-        },
-        "FreeEmailProvider" => generate_series!(internet::raw::FreeEmailProvider(EN)),
-        "DomainSuffix" => generate_series!(internet::raw::DomainSuffix(EN)),
-        "FreeEmail" => generate_series!(internet::raw::FreeEmail(EN)),
-        "SafeEmail" => generate_series!(internet::raw::SafeEmail(EN)),
-        "Username" => generate_series!(internet::raw::Username(EN)),
-        "Password" => {
-            let (start, end) = get_range_args(col_def, 8, 20)?;
-            generate_series!(internet::raw::Password(EN, start..end))
+
+        let lengths = RowSeeds::Seeded { seed: derive_seed(seed, 1), rows, first_row: 0 }
+            .map(|rng| rng.gen_range(min_items..=max_items));
+        let values = self.generate_column(items, derive_seed(seed, 0), lengths.iter().sum(), 0)?;
+        let mut builder = get_list_builder(values.dtype(), values.len(), rows, col_name)?;
+        let mut offset = 0;
+        for len in lengths {
+            builder.append_series(&values.slice(offset, len))?;
+            offset += len as i64;
         }
-        "IPv4" => generate_series!(internet::raw::IPv4(EN)),
-        "IPv6" => generate_series!(internet::raw::IPv6(EN)),
-        "IP" => generate_series!(internet::raw::IP(EN)),
-        "MACAddress" => generate_series!(internet::raw::MACAddress(EN)),
-        "UserAgent" => generate_series!(internet::raw::UserAgent(EN)),
-        #[cfg(feature = "http")]
-        "RfcStatusCode" => generate_series!(http::raw::RfcStatusCode(EN)),
-        #[cfg(feature = "http")]
-        "ValidStatusCode" => generate_series!(http::raw::ValidStatusCode(EN)),
-        #[cfg(feature = "random_color")]
-        "HexColor" => generate_series!(color::raw::HexColor(EN)),
-        #[cfg(feature = "random_color")]
-        "RgbColor" => generate_series!(color::raw::RgbColor(EN)),
-        #[cfg(feature = "random_color")]
-        "RgbaColor" => generate_series!(color::raw::RgbaColor(EN)),
-        #[cfg(feature = "random_color")]
-        "HslColor" => generate_series!(color::raw::HslColor(EN)),
-        #[cfg(feature = "random_color")]
-        "HslaColor" => generate_series!(color::raw::HslaColor(EN)),
-        #[cfg(feature = "random_color")]
-        "Color" => generate_series!(color::raw::Color(EN)),
-        "CompanySuffix" => generate_series!(company::raw::CompanySuffix(EN)),
-        "CompanyName" => generate_series!(company::raw::CompanyName(EN)),
-        "Buzzword" => generate_series!(company::raw::Buzzword(EN)),
-        "BuzzwordMiddle" => generate_series!(company::raw::BuzzwordMiddle(EN)),
-        "BuzzwordTail" => generate_series!(company::raw::BuzzwordTail(EN)),
-        "CatchPhrase" => generate_series!(company::raw::CatchPhase(EN)),
-        "BsVerb" => generate_series!(company::raw::BsVerb(EN)),
-        "BsAdj" => generate_series!(company::raw::BsAdj(EN)),
-        "BsNoun" => generate_series!(company::raw::BsNoun(EN)),
-        "Bs" => generate_series!(company::raw::Bs(EN)),
-        "Profession" => generate_series!(Profession(EN)),
-        "Industry" => generate_series!(Industry(EN)),
-        "CityPrefix" => generate_series!(address::raw::CityPrefix(EN)),
-        "CitySuffix" => generate_series!(address::raw::CitySuffix(EN)),
-        "CityName" => generate_series!(address::raw::CityName(EN)),
-        "CountryName" => generate_series!(address::raw::CountryName(EN)),
-        "CountryCode" => generate_series!(address::raw::CountryCode(EN)),
-        "StreetSuffix" => generate_series!(address::raw::StreetSuffix(EN)),
-        "StreetName" => generate_series!(address::raw::StreetName(EN)),
-        "TimeZone" => generate_series!(address::raw::TimeZone(EN)),
-        "StateName" => generate_series!(address::raw::StateName(EN)),
-        "StateAbbr" => generate_series!(address::raw::StateAbbr(EN)),
-        "SecondaryAddressType" => generate_series!(address::raw::SecondaryAddressType(EN)),
-        "SecondaryAddress" => generate_series!(address::raw::SecondaryAddress(EN)),
-        "ZipCode" => generate_series!(address::raw::ZipCode(EN)),
-        "PostCode" => generate_series!(address::raw::PostCode(EN)),
-        "BuildingNumber" => generate_series!(address::raw::BuildingNumber(EN)),
-        "Latitude" => generate_series!(address::raw::Latitude(EN)),
-        "Longitude" => generate_series!(address::raw::Longitude(EN)),
-        "Geohash" => {
-            let precision = get_args_u8(col_def, "precision")?;
-            generate_series!(address::raw::Geohash(EN, precision))
+        Ok(builder.finish().into_series())
+    }
+}
+
+/// Iterator over batches of generated rows, created by [`Generator::batches`].
+pub struct Batches<'a> {
+    generator: &'a Generator,
+    batch_size: usize,
+    next_row: usize,
+    index: u64,
+}
+
+impl Iterator for Batches<'_> {
+    type Item = Result<DataFrame, GenerateError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let remaining = self.generator.rows - self.next_row;
+        if remaining == 0 {
+            return None;
         }
-        "LicencePlate" => generate_series!(automotive::raw::LicencePlate(fake::locales::FR_FR)),
-        "Isbn" => generate_series!(barcode::raw::Isbn(EN)),
-        "Isbn13" => generate_series!(barcode::raw::Isbn13(EN)),
-        "Isbn10" => generate_series!(barcode::raw::Isbn10(EN)),
-        "PhoneNumber" => generate_series!(phone_number::raw::PhoneNumber(EN)),
-        "CellNumber" => generate_series!(phone_number::raw::CellNumber(EN)),
-        #[cfg(feature = "chrono")]
-        "Time" => generate_series!(chrono::raw::Time(EN)),
-        #[cfg(feature = "chrono")]
-        "Date" => generate_series!(chrono::raw::Date(EN)),
-        #[cfg(feature = "chrono")]
-        "DateTime" => generate_series!(chrono::raw::DateTime(EN)),
-        #[cfg(feature = "chrono")]
-        "Duration" => generate_duration_series!(chrono::raw::Duration(EN)),
-        #[cfg(feature = "chrono")]
-        "DateTimeBefore" => {
-            let dt = get_args_datetime(col_def, "dt")?;
-            generate_datetime_series!(chrono::raw::DateTimeBefore(EN, dt))
-        },
-        #[cfg(feature = "chrono")]
-        "DateTimeAfter" => {
-            let dt = get_args_datetime(col_def, "dt")?;
-            generate_datetime_series!(chrono::raw::DateTimeAfter(EN, dt))
-        },
-        #[cfg(feature = "chrono")]
-        "DateTimeBetween" => {
-            let (start, end) = get_args_datetimerange(col_def)?;
-            generate_datetime_series!(chrono::raw::DateTimeBetween(EN, start, end))
-        },
-        "FilePath" => generate_series!(filesystem::raw::FilePath(EN)),
-        "FileName" => generate_series!(filesystem::raw::FileName(EN)),
-        "FileExtension" => generate_series!(filesystem::raw::FileExtension(EN)),
-        "DirPath" => generate_series!(filesystem::raw::DirPath(EN)),
-        "Bic" => generate_series!(finance::raw::Bic(EN)),
-        #[cfg(feature = "uuid")]
-        "UUIDv1" => generate_uuid_series!(fake::uuid::UUIDv1),
-        #[cfg(feature = "uuid")]
-        "UUIDv3" => generate_uuid_series!(fake::uuid::UUIDv3),
-        #[cfg(feature = "uuid")]
-        "UUIDv4" => generate_uuid_series!(fake::uuid::UUIDv4),
-        #[cfg(feature = "uuid")]
-        "UUIDv5" => generate_uuid_series!(fake::uuid::UUIDv5),
-        "CurrencyCode" => generate_series!(currency::raw::CurrencyCode(EN)),
-        "CurrencyName" => generate_series!(currency::raw::CurrencyName(EN)),
-        "CurrencySymbol" => generate_series!(currency::raw::CurrencySymbol(EN)),
-        "CreditCardNumber" => generate_series!(creditcard::raw::CreditCardNumber(EN)),
-        #[cfg(feature = "rust_decimal")]
-        "Decimal" => generate_decimal_series!(Decimal, rust_decimal::Decimal),
-        #[cfg(feature = "rust_decimal")]
-        "PositiveDecimal" => generate_decimal_series!(PositiveDecimal, rust_decimal::Decimal),
-        #[cfg(feature = "rust_decimal")]
-        "NegativeDecimal" => generate_decimal_series!(NegativeDecimal, rust_decimal::Decimal),
-        #[cfg(feature = "rust_decimal")]
-        "NoDecimalPoints" => generate_decimal_series!(NoDecimalPoints, rust_decimal::Decimal),
-        #[cfg(feature = "bigdecimal")]
-        "BigDecimal" => generate_decimal_series!(BigDecimal, bigdecimal::BigDecimal),
-        #[cfg(feature = "bigdecimal")]
-        "PositiveBigDecimal" => generate_decimal_series!(PositiveBigDecimal, bigdecimal::BigDecimal),
-        #[cfg(feature = "bigdecimal")]
-        "NegativeBigDecimal" => generate_decimal_series!(NegativeBigDecimal, bigdecimal::BigDecimal),
-        #[cfg(feature = "bigdecimal")]
-        "NoBigDecimalPoints" => generate_decimal_series!(NoBigDecimalPoints, bigdecimal::BigDecimal),
-        _ => return Err(GenerateError::UnsupportedType(type_name.to_string())),
-    })
+        let rows = remaining.min(self.batch_size);
+        let seed = self.generator.seed.map(|seed| derive_seed(seed, self.index));
+        let first_row = self.next_row;
+        self.next_row += rows;
+        self.index += 1;
+        Some(self.generator.generate_rows(rows, first_row, seed))
+    }
 }
 
-fn get_range_args<T>(col_def: &Value, default_start: T, default_end: T) -> Result<(T, T), GenerateError>
-where
-    T: FromStr + PartialOrd,
-    <T as FromStr>::Err: std::fmt::Debug,
-{
-    let range = col_def.get("args").and_then(|a| a.get("range"));
+/// Derives an independent seed for stream `index` of `seed` (SplitMix64).
+fn derive_seed(seed: u64, index: u64) -> u64 {
+    let mut z = seed.wrapping_add(index.wrapping_add(1).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Rows generated from one RNG. Chunks are generated in parallel and each
+/// gets its own RNG derived from the column seed, so seeded output doesn't
+/// depend on the number of threads.
+const CHUNK_ROWS: usize = 1 << 16;
 
-    let parse_value = |v: &Value| -> Result<T, GenerateError> {
-        match v {
-            Value::String(s) => s.parse().map_err(|_| GenerateError::InvalidArgument("Invalid string range value".to_string())),
-            Value::Number(n) => n.to_string().parse().map_err(|_| GenerateError::InvalidArgument("Invalid numeric range value".to_string())),
-            _ => Err(GenerateError::InvalidArgument("Invalid range value type".to_string())),
+/// Where each generated row draws its randomness from.
+pub enum RowSeeds<'a> {
+    /// The given number of rows, each independently random.
+    Random(usize),
+    /// The given number of rows, reproducible from `seed`. `first_row` is the
+    /// index of the first one, for generators that number rows.
+    Seeded { seed: u64, rows: usize, first_row: usize },
+    /// One row per seed. Equal seeds produce equal values.
+    Keyed(&'a [[u8; 32]]),
+}
+
+impl RowSeeds<'_> {
+    /// Number of rows to generate.
+    pub fn len(&self) -> usize {
+        match self {
+            RowSeeds::Random(rows) | RowSeeds::Seeded { rows, .. } => *rows,
+            RowSeeds::Keyed(seeds) => seeds.len(),
         }
-    };
+    }
 
-    let start = range
-        .and_then(|r| r.get("start"))
-        .map(parse_value)
-        .transpose()?
-        .unwrap_or(default_start);
-
-    let end = range
-        .and_then(|r| r.get("end"))
-        .map(parse_value)
-        .transpose()?
-        .unwrap_or(default_end);
-
-    if start <= end {
-        Ok((start, end))
-    } else {
-        Err(GenerateError::InvalidArgument("'start' must be less than or equal to 'end'".to_string()))
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Calls `generate` in parallel with the length and RNG of each chunk of
+    /// rows, keeping the results in row order.
+    fn chunks<C, G>(&self, generate: G) -> Vec<C>
+    where
+        C: Send,
+        G: Fn(usize, &mut RowRng) -> C + Sync + Send,
+    {
+        match self {
+            RowSeeds::Random(rows) => {
+                RowSeeds::Seeded { seed: rand::thread_rng().gen(), rows: *rows, first_row: 0 }.chunks(generate)
+            }
+            RowSeeds::Seeded { seed, rows, first_row } => (0..rows.div_ceil(CHUNK_ROWS))
+                .into_par_iter()
+                .map(|chunk| {
+                    let len = CHUNK_ROWS.min(rows - chunk * CHUNK_ROWS);
+                    let rng = StdRng::seed_from_u64(derive_seed(*seed, chunk as u64));
+                    let next_row = (first_row + chunk * CHUNK_ROWS) as u64;
+                    generate(len, &mut RowRng { rng, keys: None, next_row })
+                })
+                .collect(),
+            RowSeeds::Keyed(seeds) => seeds
+                .par_chunks(CHUNK_ROWS)
+                .enumerate()
+                .map(|(chunk, keys)| {
+                    let rng = StdRng::from_seed([0; 32]);
+                    let next_row = (chunk * CHUNK_ROWS) as u64;
+                    generate(keys.len(), &mut RowRng { rng, keys: Some(keys.iter()), next_row })
+                })
+                .collect(),
+        }
+    }
+
+    fn map<T, F>(&self, f: F) -> Vec<T>
+    where
+        T: Send,
+        F: Fn(&mut StdRng) -> T + Sync + Send,
+    {
+        self.chunks(|len, rng| (0..len).map(|_| f(rng.row())).collect::<Vec<T>>())
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+
+    /// Generates one value per row with `generator`, a chunk of rows at a time.
+    pub fn generate(&self, col_name: &str, generator: &dyn ColumnGenerator) -> Result<Series, GenerateError> {
+        let chunks = self.chunks(|len, rng| generator.generate(col_name, len, rng));
+        let mut chunks = chunks.into_iter();
+        let Some(first) = chunks.next() else {
+            // Still ask the generator for its dtype.
+            return generator.generate(col_name, 0, &mut RowRng::new(StdRng::seed_from_u64(0)));
+        };
+        let mut series = first?;
+        for chunk in chunks {
+            series.append(&chunk?)?;
+        }
+        Ok(series)
     }
 }
 
-fn get_args_string(col_def: &Value, key: &str) -> Result<String, GenerateError> {
-    col_def
-        .get("args")
-        .and_then(|a| a.get(key))
-        .and_then(|v| v.as_str())
-        .map(|s| s.to_string())
-        .ok_or_else(|| GenerateError::InvalidArgument(format!("Missing '{}' argument", key)))
+/// The RNG for each row of one chunk of rows, handed to
+/// [`ColumnGenerator::generate`]: shared by the whole chunk, or reseeded per
+/// row from its key.
+pub struct RowRng<'a> {
+    rng: StdRng,
+    keys: Option<std::slice::Iter<'a, [u8; 32]>>,
+    next_row: u64,
 }
 
-fn get_args_u8(col_def: &Value, key: &str) -> Result<u8, GenerateError> {
-    col_def
-        .get("args")
-        .and_then(|a| a.get(key))
-        .and_then(|v| v.as_u64())
-        .map(|v| v as u8)
-        .ok_or_else(|| GenerateError::InvalidArgument(format!("Invalid '{}' argument", key)))
+impl RowRng<'_> {
+    /// An RNG shared by every row, for calling a generator directly.
+    pub fn new(rng: StdRng) -> Self {
+        RowRng { rng, keys: None, next_row: 0 }
+    }
+
+    /// Moves to the next row and returns its RNG.
+    pub fn row(&mut self) -> &mut StdRng {
+        if let Some(seed) = self.keys.as_mut().and_then(Iterator::next) {
+            self.rng = StdRng::from_seed(*seed);
+        }
+        self.next_row += 1;
+        &mut self.rng
+    }
+
+    /// Index within the column of the row [`RowRng::row`] last moved to.
+    /// Batches continue the numbering of the batches before them.
+    pub fn index(&self) -> u64 {
+        self.next_row.saturating_sub(1)
+    }
 }
 
-#[cfg(feature = "chrono")]
-fn get_args_datetime(col_def: &Value, key: &str) -> Result<DateTime<Utc>, GenerateError> {
-    col_def
-        .get("args")
-        .and_then(|a| a.get(key))
-        .and_then(|v| v.as_str())
-        .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
-        .map(|dt| dt.with_timezone(&Utc))
-        .ok_or_else(|| GenerateError::InvalidArgument(format!("Invalid '{}' datetime", key)))
+/// Generates the column `col_name` of the built-in type `type_name`, one
+/// value per row of `seeds`.
+pub fn create_series_from_type(
+    type_name: &str,
+    col_name: &str,
+    seeds: &RowSeeds,
+    col_def: &Value,
+) -> Result<Series, GenerateError> {
+    registry::builtins().create_series(type_name, col_name, seeds, col_def)
 }
 
-#[cfg(feature = "chrono")]
-fn get_args_datetimerange(col_def: &Value) -> Result<(DateTime<Utc>, DateTime<Utc>), GenerateError> {
-    let start = get_args_datetime(col_def, "start")?;
-    let end = get_args_datetime(col_def, "end")?;
-    if start <= end {
-        Ok((start, end))
-    } else {
-        Err(GenerateError::InvalidArgument("Invalid datetime range".to_string()))
+/// Rounds of replacing duplicates before a `"unique": true` column gives up.
+const UNIQUE_ATTEMPTS: u64 = 16;
+
+/// Drops duplicate values and tops the column back up with values from
+/// `more(attempt, rows)` until it has `rows` distinct values. Each round asks
+/// for more values than are missing, since they collide more often as the
+/// column fills the space of possible values.
+fn make_unique<F>(mut series: Series, rows: usize, more: F) -> Result<Series, GenerateError>
+where
+    F: Fn(u64, usize) -> Result<Series, GenerateError>,
+{
+    for attempt in 0..=UNIQUE_ATTEMPTS {
+        series = series.take(&series.arg_unique()?)?;
+        if series.len() >= rows {
+            return Ok(series.head(Some(rows)));
+        }
+        if attempt < UNIQUE_ATTEMPTS {
+            series.append(&more(attempt, (rows - series.len()) << attempt.min(10))?)?;
+        }
     }
-}
\ No newline at end of file
+    Err(GenerateError::InvalidArgument(format!(
+        "Couldn't generate {} unique values for '{}'; only found {}",
+        rows,
+        series.name(),
+        series.len()
+    )))
+}
+
+/// Truncates string values to `max_length` characters.
+fn apply_max_length(series: Series, col_def: &Value) -> Result<Series, GenerateError> {
+    let Some(max_length) = col_def.get("max_length") else {
+        return Ok(series);
+    };
+    let max_length = max_length
+        .as_u64()
+        .ok_or_else(|| GenerateError::InvalidArgument("'max_length' must be a non-negative integer".to_string()))?
+        as usize;
+    let ca = series.str().map_err(|_| {
+        GenerateError::InvalidArgument(format!("'max_length' only applies to string columns, not {}", series.dtype()))
+    })?;
+    let truncated = ca.apply_values(|value| match value.char_indices().nth(max_length) {
+        Some((end, _)) => Cow::Borrowed(&value[..end]),
+        None => Cow::Borrowed(value),
+    });
+    Ok(truncated.into_series())
+}
+
+/// Replaces a random `null_ratio` fraction of the generated values with nulls.
+fn apply_null_ratio(series: Series, col_def: &Value, seeds: &RowSeeds) -> Result<Series, GenerateError> {
+    let Some(ratio) = col_def.get("null_ratio") else {
+        return Ok(series);
+    };
+    let ratio = ratio
+        .as_f64()
+        .filter(|r| (0.0..=1.0).contains(r))
+        .ok_or_else(|| GenerateError::InvalidArgument("'null_ratio' must be a number between 0 and 1".to_string()))?;
+
+    let keep: BooleanChunked = seeds.map(|rng| !rng.gen_bool(ratio)).into_iter().collect();
+    let nulls = Series::full_null(series.name(), series.len(), series.dtype());
+    Ok(series.zip_with(&keep, &nulls)?)
+}
@@ -0,0 +1,353 @@
+use std::collections::HashMap;
+
+use polars::export::num::NumCast;
+use polars::prelude::*;
+use serde_json::{json, Map, Value};
+use thiserror::Error;
+
+use crate::ddl::decimal_type;
+
+#[cfg(feature = "chrono")]
+use chrono::DateTime;
+
+#[derive(Error, Debug)]
+pub enum InferError {
+    #[error("Polars error: {0}")]
+    Polars(#[from] polars::error::PolarsError),
+}
+
+/// String columns with at most this many distinct values become a `Choice`.
+const MAX_CHOICE_VALUES: usize = 32;
+
+/// Number of non-null values checked when matching string patterns.
+const PATTERN_SAMPLE_SIZE: usize = 1000;
+
+/// Builds a schema that generates data resembling `df`, using each column's
+/// dtype, value range, null ratio, cardinality and string patterns.
+///
+/// ```
+/// use polars::prelude::*;
+/// use rsfake::infer::infer_schema;
+///
+/// let df = df!("email" => ["a@example.com", "b@example.org"], "age" => [31i64, 58])?;
+/// let schema = infer_schema(&df)?;
+/// assert_eq!(schema["columns"][0]["type"], "SafeEmail");
+/// assert_eq!(schema["columns"][1]["args"]["range"]["end"], 58);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn infer_schema(df: &DataFrame) -> Result<Value, InferError> {
+    let columns = df
+        .get_columns()
+        .iter()
+        .map(infer_column)
+        .collect::<Result<Vec<Value>, InferError>>()?;
+    Ok(json!({ "columns": columns }))
+}
+
+fn infer_column(series: &Series) -> Result<Value, InferError> {
+    // The `items` of an `Array` or the `columns` of an `Object`.
+    let mut children = None;
+    let (col_type, args) = match series.dtype() {
+        DataType::UInt8 => numeric_range::<u8>("u8", series)?,
+        DataType::UInt16 => numeric_range::<u16>("u16", series)?,
+        DataType::UInt32 => numeric_range::<u32>("u32", series)?,
+        DataType::UInt64 => numeric_range::<u64>("u64", series)?,
+        DataType::Int8 => numeric_range::<i8>("i8", series)?,
+        DataType::Int16 => numeric_range::<i16>("i16", series)?,
+        DataType::Int32 => numeric_range::<i32>("i32", series)?,
+        DataType::Int64 => numeric_range::<i64>("i64", series)?,
+        DataType::Float32 => numeric_range::<f32>("f32", series)?,
+        DataType::Float64 => numeric_range::<f64>("f64", series)?,
+        DataType::Decimal(precision, scale) => {
+            let (col_type, args) = decimal_type(precision.unwrap_or(38) as u64, scale.unwrap_or(0) as u64);
+            let (_, range) = numeric_range::<f64>(col_type, &series.cast(&DataType::Float64)?)?;
+            match (args, range) {
+                (Some(Value::Object(mut args)), Some(Value::Object(range))) => {
+                    args.extend(range);
+                    (col_type, Some(Value::Object(args)))
+                }
+                (None, range) => (col_type, range),
+                (args, _) => (col_type, args),
+            }
+        }
+        DataType::Boolean => {
+            let ca = series.bool()?;
+            let non_null = ca.len() - ca.null_count();
+            let trues = ca.sum().unwrap_or(0) as usize;
+            let ratio = (trues * 100 + non_null / 2).checked_div(non_null).unwrap_or(50);
+            ("Boolean", Some(json!({ "ratio": ratio })))
+        }
+        DataType::Date => ("Date", None),
+        DataType::Time => ("Time", None),
+        DataType::Duration(_) => ("Duration", None),
+        #[cfg(feature = "chrono")]
+        DataType::Datetime(_, _) => {
+            let millis = series.cast(&DataType::Datetime(TimeUnit::Milliseconds, None))?.cast(&DataType::Int64)?;
+            datetime_range(millis.i64()?.min(), millis.i64()?.max())
+        }
+        DataType::String => infer_string_column(series.str()?),
+        DataType::List(_) => {
+            let lists = series.list()?;
+            let mut items = infer_column(&lists.get_inner().with_name(series.name()))?;
+            if let Value::Object(items) = &mut items {
+                items.shift_remove("name");
+            }
+            children = Some(("items", items));
+            let lengths: Vec<usize> = lists.into_iter().flatten().map(|list| list.len()).collect();
+            let args = match (lengths.iter().min(), lengths.iter().max()) {
+                (Some(min), Some(max)) => Some(json!({ "min_items": min, "max_items": max })),
+                _ => None,
+            };
+            ("Array", args)
+        }
+        DataType::Struct(_) => {
+            let fields = series
+                .struct_()?
+                .fields()
+                .iter()
+                .map(infer_column)
+                .collect::<Result<Vec<Value>, InferError>>()?;
+            children = Some(("columns", Value::Array(fields)));
+            ("Object", None)
+        }
+        _ => ("Word", None),
+    };
+
+    let mut column = Map::new();
+    column.insert("name".to_string(), json!(series.name()));
+    column.insert("type".to_string(), json!(col_type));
+    if let Some(args) = args {
+        column.insert("args".to_string(), args);
+    }
+    if let Some((key, children)) = children {
+        column.insert(key.to_string(), children);
+    }
+    // Struct columns can't be null as a whole.
+    if series.null_count() > 0 && col_type != "Object" {
+        let ratio = series.null_count() as f64 / series.len() as f64;
+        column.insert("null_ratio".to_string(), json!((ratio * 10_000.0).round() / 10_000.0));
+    }
+    Ok(Value::Object(column))
+}
+
+fn numeric_range<T>(col_type: &'static str, series: &Series) -> Result<(&'static str, Option<Value>), InferError>
+where
+    T: NumCast + serde::Serialize,
+{
+    let range = match (series.min::<T>()?, series.max::<T>()?) {
+        (Some(start), Some(end)) => Some(json!({ "range": { "start": start, "end": end } })),
+        _ => None,
+    };
+    Ok((col_type, range))
+}
+
+#[cfg(feature = "chrono")]
+fn datetime_range(min: Option<i64>, max: Option<i64>) -> (&'static str, Option<Value>) {
+    let to_rfc3339 = |millis: Option<i64>| millis.and_then(DateTime::from_timestamp_millis).map(|dt| dt.to_rfc3339());
+    match (to_rfc3339(min), to_rfc3339(max)) {
+        (Some(start), Some(end)) => ("DateTimeBetween", Some(json!({ "start": start, "end": end }))),
+        _ => ("DateTime", None),
+    }
+}
+
+fn infer_string_column(ca: &StringChunked) -> (&'static str, Option<Value>) {
+    let values: Vec<&str> = ca.into_iter().flatten().collect();
+    if values.is_empty() {
+        return ("Word", None);
+    }
+
+    let sample = &values[..values.len().min(PATTERN_SAMPLE_SIZE)];
+    if sample.iter().all(|v| looks_like_email(v)) {
+        return ("SafeEmail", None);
+    }
+    #[cfg(feature = "uuid")]
+    if sample.iter().all(|v| looks_like_uuid(v)) {
+        return ("UUIDv4", None);
+    }
+    if sample.iter().all(|v| looks_like_ipv4(v)) {
+        return ("IPv4", None);
+    }
+
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for value in &values {
+        *counts.entry(value).or_default() += 1;
+    }
+    if counts.len() <= MAX_CHOICE_VALUES && counts.len() * 2 <= values.len() {
+        let mut counts: Vec<(&str, usize)> = counts.into_iter().collect();
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        let (choices, weights): (Vec<&str>, Vec<usize>) = counts.into_iter().unzip();
+        return ("Choice", Some(json!({ "values": choices, "weights": weights })));
+    }
+
+    #[cfg(feature = "chrono")]
+    if sample.iter().all(|v| DateTime::parse_from_rfc3339(v).is_ok()) {
+        let millis = values
+            .iter()
+            .filter_map(|v| DateTime::parse_from_rfc3339(v).ok())
+            .map(|dt| dt.timestamp_millis());
+        return datetime_range(millis.clone().min(), millis.max());
+    }
+
+    let word_counts = values.iter().map(|v| v.split_whitespace().count());
+    let (min_words, max_words) = (word_counts.clone().min().unwrap_or(0), word_counts.max().unwrap_or(0));
+    if max_words <= 1 {
+        ("Word", None)
+    } else {
+        ("Sentence", Some(json!({ "range": { "start": min_words.max(1), "end": max_words + 1 } })))
+    }
+}
+
+fn looks_like_email(value: &str) -> bool {
+    match value.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && !domain.contains('@')
+                && domain.contains('.')
+                && !domain.starts_with('.')
+                && !domain.ends_with('.')
+                && !value.contains(char::is_whitespace)
+        }
+        None => false,
+    }
+}
+
+#[cfg(feature = "uuid")]
+fn looks_like_uuid(value: &str) -> bool {
+    value.len() == 36
+        && value.char_indices().all(|(i, c)| match i {
+            8 | 13 | 18 | 23 => c == '-',
+            _ => c.is_ascii_hexdigit(),
+        })
+}
+
+fn looks_like_ipv4(value: &str) -> bool {
+    let parts: Vec<&str> = value.split('.').collect();
+    parts.len() == 4 && parts.iter().all(|p| !p.is_empty() && p.len() <= 3 && p.parse::<u8>().is_ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column(series: Series) -> Value {
+        infer_column(&series).unwrap()
+    }
+
+    #[test]
+    fn numeric_ranges_come_from_min_and_max() {
+        let c = column(Series::new("n", &[Some(5i32), None, Some(-3), Some(12)]));
+        assert_eq!(c["type"], "i32");
+        assert_eq!(c["args"]["range"], json!({ "start": -3, "end": 12 }));
+        assert_eq!(c["null_ratio"], 0.25);
+        let c = column(Series::new("n", &[0.5f64, 2.25]));
+        assert_eq!(c["args"]["range"], json!({ "start": 0.5, "end": 2.25 }));
+        let c = column(Series::new("n", &[None::<i64>, None]));
+        assert_eq!(c["type"], "i64");
+        assert!(c.get("args").is_none());
+        assert_eq!(c["null_ratio"], 1.0);
+    }
+
+    #[test]
+    fn small_integers_keep_their_dtype() {
+        let c = column(Series::new("n", &[-3i8, 100]));
+        assert_eq!(c["type"], "i8");
+        assert_eq!(c["args"]["range"], json!({ "start": -3, "end": 100 }));
+        assert_eq!(column(Series::new("n", &[-300i16]))["type"], "i16");
+        assert_eq!(column(Series::new("n", &[7u8]))["type"], "u8");
+        let c = column(Series::new("n", &[40_000u16, 65_535]));
+        assert_eq!(c["type"], "u16");
+        assert_eq!(c["args"]["range"], json!({ "start": 40_000, "end": 65_535 }));
+    }
+
+    #[test]
+    fn decimals_keep_precision_scale_and_range() {
+        let decimals = Int128Chunked::from_vec("d", vec![-150, 1999])
+            .into_decimal_unchecked(Some(6), 2)
+            .into_series();
+        let c = column(decimals);
+        assert_eq!(c["type"], "decimal128");
+        assert_eq!(c["args"], json!({ "precision": 6, "scale": 2, "range": { "start": -1.5, "end": 19.99 } }));
+    }
+
+    #[test]
+    fn temporal_dtypes() {
+        let dates = Series::new("d", &[19_000i32]).cast(&DataType::Date).unwrap();
+        assert_eq!(column(dates)["type"], "Date");
+        let times = Series::new("t", &[3_600_000_000_000i64]).cast(&DataType::Time).unwrap();
+        assert_eq!(column(times)["type"], "Time");
+        let durations = Series::new("t", &[1_000i64]).cast(&DataType::Duration(TimeUnit::Milliseconds)).unwrap();
+        assert_eq!(column(durations)["type"], "Duration");
+    }
+
+    #[test]
+    fn lists_become_arrays() {
+        let lists = Series::new("tags", &[Series::new("", &["a", "b"]), Series::new("", &["a"]), Series::new("", &["a", "b", "a"])]);
+        let c = column(lists);
+        assert_eq!(c["type"], "Array");
+        assert_eq!(c["args"], json!({ "min_items": 1, "max_items": 3 }));
+        assert_eq!(c["items"]["type"], "Choice");
+        assert!(c["items"].get("name").is_none());
+    }
+
+    #[test]
+    fn structs_become_objects() {
+        let fields = df!("id" => [1i16, 2], "email" => [Some("a@b.com"), None]).unwrap();
+        let c = column(fields.into_struct("user").into_series());
+        assert_eq!(c["type"], "Object");
+        assert_eq!(c["columns"][0]["type"], "i16");
+        assert_eq!(c["columns"][1]["type"], "SafeEmail");
+        assert_eq!(c["columns"][1]["null_ratio"], 0.5);
+        assert!(c.get("null_ratio").is_none());
+    }
+
+    #[test]
+    fn boolean_ratio_rounds_to_a_percentage() {
+        let c = column(Series::new("b", &[Some(true), Some(false), Some(false), None]));
+        assert_eq!(c["args"]["ratio"], 33);
+        let c = column(Series::new("b", &[None::<bool>]));
+        assert_eq!(c["args"]["ratio"], 50);
+    }
+
+    #[test]
+    fn null_ratio_is_rounded_to_four_places() {
+        let values: Vec<Option<i32>> = (0..3).map(|i| (i > 0).then_some(i)).collect();
+        assert_eq!(column(Series::new("n", values))["null_ratio"], 0.3333);
+    }
+
+    #[test]
+    fn string_patterns() {
+        assert_eq!(column(Series::new("s", &["a@b.com", "c.d@e.org"]))["type"], "SafeEmail");
+        assert_eq!(column(Series::new("s", &["10.0.0.1", "192.168.1.255"]))["type"], "IPv4");
+        assert_eq!(column(Series::new("s", &["10.0.0.256", "1.2.3"]))["type"], "Word");
+        assert_eq!(column(Series::new("s", &["a@b", "c@d.org"]))["type"], "Word");
+        #[cfg(feature = "uuid")]
+        assert_eq!(column(Series::new("s", &["67e55044-10b1-426f-9247-bb680e5fe0c8"]))["type"], "UUIDv4");
+    }
+
+    #[test]
+    fn repeated_strings_become_weighted_choices() {
+        let c = column(Series::new("s", &["b", "a", "b", "a", "b", "c"]));
+        assert_eq!(c["type"], "Choice");
+        assert_eq!(c["args"], json!({ "values": ["b", "a", "c"], "weights": [3, 2, 1] }));
+        assert_eq!(column(Series::new("s", &["x", "y", "z"]))["type"], "Word");
+    }
+
+    #[test]
+    fn sentences_keep_their_word_counts() {
+        let c = column(Series::new("s", &["one two", "one two three four", "five"]));
+        assert_eq!(c["type"], "Sentence");
+        assert_eq!(c["args"]["range"], json!({ "start": 1, "end": 5 }));
+        assert_eq!(column(Series::new("s", &[None::<&str>]))["type"], "Word");
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn datetimes_become_datetime_ranges() {
+        let c = column(Series::new("s", &["2024-01-01T00:00:00Z", "2023-06-01T12:00:00+02:00", "2024-03-01T00:00:00Z"]));
+        assert_eq!(c["type"], "DateTimeBetween");
+        assert_eq!(c["args"], json!({ "start": "2023-06-01T10:00:00+00:00", "end": "2024-03-01T00:00:00+00:00" }));
+        let millis = Series::new("d", &[0i64, 86_400_000]).cast(&DataType::Datetime(TimeUnit::Milliseconds, None)).unwrap();
+        let c = column(millis);
+        assert_eq!(c["args"]["end"], "1970-01-02T00:00:00+00:00");
+    }
+}
@@ -0,0 +1,537 @@
+use serde_json::{json, Map, Value};
+use thiserror::Error;
+
+/// Nesting depth at which a schema is assumed to refer to itself.
+const MAX_DEPTH: usize = 32;
+
+/// Non-negative ISO 8601 durations, for the `duration` format.
+const ISO_DURATION_PATTERN: &str = "P[0-9]{1,3}DT(1?[0-9]|2[0-3])H[1-5]?[0-9]M[1-5]?[0-9]S";
+
+#[derive(Error, Debug)]
+pub enum JsonSchemaError {
+    #[error("The root schema must be an object with 'properties'")]
+    NotAnObject,
+    #[error("Can't resolve $ref '{0}'; only local refs such as '#/$defs/name' are supported")]
+    UnresolvedRef(String),
+    #[error("'{0}' nests more than {MAX_DEPTH} levels deep; recursive schemas aren't supported")]
+    TooDeep(String),
+}
+
+/// The schema imported from a JSON Schema document.
+#[derive(Debug)]
+pub struct ImportedSchema {
+    pub schema: Value,
+    /// Properties that were skipped or only approximated.
+    pub warnings: Vec<String>,
+}
+
+/// Builds a schema that generates objects valid against a JSON Schema
+/// (draft 2020-12) document. Each of the root's `properties` becomes a
+/// column: `type` and `format` pick the generator, `enum` and `const` a
+/// `Choice`, `minimum`/`maximum` a range, `minLength`/`maxLength` a length
+/// limit and `pattern` a `Regex`. Nested objects and arrays become `Object`
+/// and `Array` columns. Every property is generated, so `required` ones are
+/// always present; properties that allow `null` get `null_ratio` when it's
+/// above zero. Local `$ref`s and `allOf` are resolved, and `anyOf`/`oneOf`
+/// use their first variant.
+///
+/// ```
+/// use rsfake::jsonschema::schema_from_json_schema;
+/// use serde_json::json;
+///
+/// let imported = schema_from_json_schema(&json!({
+///     "type": "object",
+///     "required": ["id", "email"],
+///     "properties": {
+///         "id": { "type": "integer", "minimum": 1, "maximum": 1000 },
+///         "email": { "type": "string", "format": "email" },
+///         "plan": { "enum": ["free", "pro"] },
+///         "nickname": { "type": ["string", "null"], "maxLength": 12 }
+///     }
+/// }), 0.1)?;
+/// let columns = &imported.schema["columns"];
+/// assert_eq!(columns[0]["args"]["range"]["end"], 1000);
+/// assert_eq!(columns[1]["type"], "SafeEmail");
+/// assert_eq!(columns[2]["type"], "Choice");
+/// assert_eq!(columns[3]["max_length"], 12);
+/// assert_eq!(columns[3]["null_ratio"], 0.1);
+/// # Ok::<(), rsfake::jsonschema::JsonSchemaError>(())
+/// ```
+pub fn schema_from_json_schema(json_schema: &Value, null_ratio: f64) -> Result<ImportedSchema, JsonSchemaError> {
+    let mut importer = Importer { root: json_schema, null_ratio, warnings: Vec::new() };
+    let root = importer.resolve(json_schema, "#", 0)?;
+    let properties = root
+        .get("properties")
+        .and_then(Value::as_object)
+        .filter(|properties| !properties.is_empty())
+        .ok_or(JsonSchemaError::NotAnObject)?;
+
+    let mut columns = Vec::new();
+    for (name, property) in properties {
+        if let Some(column) = importer.column(name, property, name, 1)? {
+            columns.push(column);
+        }
+    }
+    Ok(ImportedSchema { schema: json!({ "columns": columns }), warnings: importer.warnings })
+}
+
+struct Importer<'a> {
+    root: &'a Value,
+    null_ratio: f64,
+    warnings: Vec<String>,
+}
+
+impl Importer<'_> {
+    /// The column for one property, or `None` if it can't be generated.
+    fn column(&mut self, name: &str, schema: &Value, path: &str, depth: usize) -> Result<Option<Value>, JsonSchemaError> {
+        if depth > MAX_DEPTH {
+            return Err(JsonSchemaError::TooDeep(path.to_string()));
+        }
+        let mut schema = self.resolve(schema, path, depth)?;
+        let mut nullable = false;
+
+        // anyOf/oneOf generate their first non-null variant.
+        for key in ["anyOf", "oneOf"] {
+            let Some(Value::Array(variants)) = schema.remove(key) else {
+                continue;
+            };
+            let mut chosen = None;
+            for variant in &variants {
+                let variant = self.resolve(variant, path, depth)?;
+                if variant.get("type").and_then(Value::as_str) == Some("null") {
+                    nullable = true;
+                } else if chosen.is_none() {
+                    chosen = Some(variant);
+                }
+            }
+            if let Some(variant) = chosen {
+                merge(&mut schema, variant);
+            }
+        }
+
+        let type_name = match schema.get("type") {
+            Some(Value::String(t)) => Some(t.as_str()),
+            Some(Value::Array(types)) => {
+                nullable |= types.iter().any(|t| t == "null");
+                types.iter().filter_map(Value::as_str).find(|t| *t != "null")
+            }
+            _ if schema.contains_key("properties") => Some("object"),
+            _ if schema.contains_key("items") => Some("array"),
+            _ => None,
+        };
+
+        let mut column = Map::new();
+        column.insert("name".to_string(), json!(name));
+        let values = match (schema.get("const"), schema.get("enum")) {
+            (Some(value), _) => Some(vec![value.clone()]),
+            (None, Some(Value::Array(values))) => {
+                nullable |= values.iter().any(Value::is_null);
+                Some(values.iter().filter(|v| !v.is_null()).cloned().collect())
+            }
+            _ => None,
+        };
+        match (values, type_name) {
+            (Some(values), _) if values.is_empty() => {
+                self.warnings.push(format!("{}: enum has no non-null values, skipped", path));
+                return Ok(None);
+            }
+            (Some(values), _) => {
+                column.insert("type".to_string(), json!("Choice"));
+                column.insert("args".to_string(), json!({ "values": values }));
+            }
+            (None, Some("string")) => self.string_column(&mut column, &schema, path),
+            (None, Some(numeric @ ("integer" | "number"))) => {
+                let integer = numeric == "integer";
+                column.insert("type".to_string(), json!(if integer { "i64" } else { "f64" }));
+                match range(&schema, integer) {
+                    Some(Ok(range)) => {
+                        column.insert("args".to_string(), json!({ "range": range }));
+                    }
+                    Some(Err(())) => {
+                        self.warnings.push(format!("{}: no {} is within minimum and maximum, skipped", path, numeric));
+                        return Ok(None);
+                    }
+                    None => {}
+                }
+                if schema.contains_key("multipleOf") {
+                    self.warnings.push(format!("{}: multipleOf isn't supported, ignored", path));
+                }
+            }
+            (None, Some("boolean")) => {
+                column.insert("type".to_string(), json!("Boolean"));
+                column.insert("args".to_string(), json!({ "ratio": 50 }));
+            }
+            (None, Some("object")) => {
+                let mut fields = Vec::new();
+                if let Some(properties) = schema.get("properties").and_then(Value::as_object) {
+                    for (field, property) in properties {
+                        if let Some(field) = self.column(field, property, &format!("{}.{}", path, field), depth + 1)? {
+                            fields.push(field);
+                        }
+                    }
+                }
+                if fields.is_empty() {
+                    self.warnings.push(format!("{}: object has no properties, skipped", path));
+                    return Ok(None);
+                }
+                column.insert("type".to_string(), json!("Object"));
+                column.insert("columns".to_string(), Value::Array(fields));
+            }
+            (None, Some("array")) => {
+                let items = schema.get("items").cloned().unwrap_or(Value::Bool(true));
+                let Some(items) = self.column(name, &items, &format!("{}[]", path), depth + 1)? else {
+                    return Ok(None);
+                };
+                column.insert("type".to_string(), json!("Array"));
+                let mut args = Map::new();
+                if let Some(min) = schema.get("minItems").and_then(Value::as_u64) {
+                    args.insert("min_items".to_string(), json!(min));
+                }
+                if let Some(max) = schema.get("maxItems").and_then(Value::as_u64) {
+                    args.insert("max_items".to_string(), json!(max));
+                }
+                if !args.is_empty() {
+                    column.insert("args".to_string(), Value::Object(args));
+                }
+                column.insert("items".to_string(), items);
+            }
+            (None, Some("null")) => {
+                self.warnings.push(format!("{}: type null is always null, skipped", path));
+                return Ok(None);
+            }
+            (None, other) => {
+                if let Some(other) = other {
+                    self.warnings.push(format!("{}: unknown type '{}', using Word", path, other));
+                }
+                column.insert("type".to_string(), json!("Word"));
+            }
+        }
+
+        if nullable && self.null_ratio > 0.0 && column["type"] == "Object" {
+            self.warnings.push(format!("{}: Object columns can't be null, so null is never generated", path));
+        } else if nullable && self.null_ratio > 0.0 {
+            column.insert("null_ratio".to_string(), json!(self.null_ratio));
+        }
+        Ok(Some(Value::Object(column)))
+    }
+
+    fn string_column(&mut self, column: &mut Map<String, Value>, schema: &Map<String, Value>, path: &str) {
+        let min_length = schema.get("minLength").and_then(Value::as_u64).unwrap_or(0);
+        let max_length = schema.get("maxLength").and_then(Value::as_u64);
+        let format = schema.get("format").and_then(Value::as_str);
+        let type_name = match format {
+            Some("email" | "idn-email") => Some("SafeEmail"),
+            Some("uuid") => Some("UUIDv4"),
+            Some("date-time") => Some("DateTime"),
+            Some("date") => Some("Date"),
+            Some("time") => Some("Time"),
+            Some("duration") => None,
+            Some("ipv4") => Some("IPv4"),
+            Some("ipv6") => Some("IPv6"),
+            Some("uri" | "uri-reference" | "iri" | "iri-reference") => Some("Url"),
+            Some("hostname" | "idn-hostname") => Some("Word"),
+            Some(other) => {
+                self.warnings.push(format!("{}: unknown format '{}', ignored", path, other));
+                None
+            }
+            None => None,
+        };
+
+        // The `Duration` faker can be negative, which isn't a valid ISO 8601 duration.
+        let pattern = match format {
+            Some("duration") => Some(ISO_DURATION_PATTERN),
+            _ => schema.get("pattern").and_then(Value::as_str).filter(|_| type_name.is_none()),
+        };
+        if let Some(pattern) = pattern {
+            column.insert("type".to_string(), json!("Regex"));
+            column.insert("args".to_string(), json!({ "pattern": pattern }));
+        } else if let Some(type_name) = type_name.filter(|&t| t != "Word") {
+            column.insert("type".to_string(), json!(type_name));
+            // JSON holds dates and times as RFC 3339 text rather than native values.
+            if matches!(type_name, "DateTime" | "Date" | "Time") {
+                column.insert("as_string".to_string(), json!(true));
+            }
+            // Cutting an email or a URI short would make it invalid.
+            if max_length.is_some() {
+                self.warnings.push(format!(
+                    "{}: maxLength isn't applied to format '{}' values, they may be longer",
+                    path,
+                    format.unwrap_or_default()
+                ));
+            }
+        } else if type_name.is_some() || min_length <= 1 {
+            column.insert("type".to_string(), json!("Word"));
+            if let Some(max_length) = max_length {
+                column.insert("max_length".to_string(), json!(max_length));
+            }
+        } else {
+            // Words can be shorter than minLength, so pad with letters instead.
+            let max_length = max_length.unwrap_or(min_length + 16);
+            column.insert("type".to_string(), json!("Regex"));
+            column.insert("args".to_string(), json!({ "pattern": format!("[a-z]{{{},{}}}", min_length, max_length) }));
+        }
+    }
+
+    /// Follows `$ref` and merges `allOf`, returning the schema's keywords.
+    fn resolve(&self, schema: &Value, path: &str, depth: usize) -> Result<Map<String, Value>, JsonSchemaError> {
+        if depth > MAX_DEPTH {
+            return Err(JsonSchemaError::TooDeep(path.to_string()));
+        }
+        let mut schema = match schema {
+            Value::Object(schema) => schema.clone(),
+            // `true` accepts anything; treat it as a string.
+            _ => Map::new(),
+        };
+        if let Some(Value::String(reference)) = schema.remove("$ref") {
+            let target = reference
+                .strip_prefix('#')
+                .and_then(|pointer| self.root.pointer(pointer))
+                .ok_or_else(|| JsonSchemaError::UnresolvedRef(reference.clone()))?;
+            let target = self.resolve(target, path, depth + 1)?;
+            merge(&mut schema, target);
+        }
+        if let Some(Value::Array(all)) = schema.remove("allOf") {
+            for sub in &all {
+                let sub = self.resolve(sub, path, depth + 1)?;
+                merge(&mut schema, sub);
+            }
+        }
+        Ok(schema)
+    }
+}
+
+/// Adds `other`'s keywords to `schema`, keeping `schema`'s where both are set
+/// but combining their `properties` and `required`.
+fn merge(schema: &mut Map<String, Value>, other: Map<String, Value>) {
+    for (key, value) in other {
+        match (schema.get_mut(&key), value) {
+            (Some(Value::Object(ours)), Value::Object(theirs)) if key == "properties" => {
+                for (name, property) in theirs {
+                    ours.entry(name).or_insert(property);
+                }
+            }
+            (Some(Value::Array(ours)), Value::Array(theirs)) if key == "required" => {
+                for name in theirs {
+                    if !ours.contains(&name) {
+                        ours.push(name);
+                    }
+                }
+            }
+            (Some(_), _) => {}
+            (None, value) => {
+                schema.insert(key, value);
+            }
+        }
+    }
+}
+
+/// The inclusive `{ "start", "end" }` range allowed by `minimum`, `maximum`
+/// and their exclusive forms, which are moved inward to the next integer or
+/// float. A missing bound is set 1000 from the other, and bounds that no
+/// value satisfies are an `Err`.
+fn range(schema: &Map<String, Value>, integer: bool) -> Option<Result<Value, ()>> {
+    let bound = |key| schema.get(key).and_then(Value::as_f64);
+    // Draft 4 marks `minimum` and `maximum` exclusive with booleans instead.
+    let exclusive = |key| schema.get(key).and_then(Value::as_bool).unwrap_or(false);
+    let (minimum, maximum) = (bound("minimum"), bound("maximum"));
+    let exclusive_min = bound("exclusiveMinimum").or(minimum.filter(|_| exclusive("exclusiveMinimum")));
+    let exclusive_max = bound("exclusiveMaximum").or(maximum.filter(|_| exclusive("exclusiveMaximum")));
+
+    // With both an inclusive and an exclusive bound, the tighter one wins.
+    let start = [
+        minimum.map(|min| if integer { min.ceil() } else { min }),
+        exclusive_min.map(|min| if integer { min.floor() + 1.0 } else { min.next_up() }),
+    ]
+    .into_iter()
+    .flatten()
+    .reduce(f64::max);
+    let end = [
+        maximum.map(|max| if integer { max.floor() } else { max }),
+        exclusive_max.map(|max| if integer { max.ceil() - 1.0 } else { max.next_down() }),
+    ]
+    .into_iter()
+    .flatten()
+    .reduce(f64::min);
+    let (start, end) = match (start, end) {
+        (None, None) => return None,
+        (Some(start), None) => (start, start + 1000.0),
+        (None, Some(end)) => (end - 1000.0, end),
+        (Some(start), Some(end)) if start <= end => (start, end),
+        (Some(_), Some(_)) => return Some(Err(())),
+    };
+    Some(Ok(if integer {
+        json!({ "start": start as i64, "end": end as i64 })
+    } else {
+        json!({ "start": start, "end": end })
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn import(properties: Value) -> ImportedSchema {
+        schema_from_json_schema(&json!({ "type": "object", "properties": properties }), 0.2).unwrap()
+    }
+
+    fn column(property: Value) -> Value {
+        import(json!({ "p": property })).schema["columns"][0].clone()
+    }
+
+    #[test]
+    fn exclusive_integer_bounds_move_inward() {
+        let c = column(json!({ "type": "integer", "exclusiveMinimum": 0, "exclusiveMaximum": 10 }));
+        assert_eq!(c["args"]["range"], json!({ "start": 1, "end": 9 }));
+        let c = column(json!({ "type": "integer", "exclusiveMinimum": 0.5, "exclusiveMaximum": 9.5 }));
+        assert_eq!(c["args"]["range"], json!({ "start": 1, "end": 9 }));
+        let c = column(json!({ "type": "integer", "minimum": 0.5, "maximum": 9.5 }));
+        assert_eq!(c["args"]["range"], json!({ "start": 1, "end": 9 }));
+    }
+
+    #[test]
+    fn exclusive_number_bounds_move_inward() {
+        let c = column(json!({ "type": "number", "exclusiveMinimum": 0, "exclusiveMaximum": 1 }));
+        let (start, end) = (c["args"]["range"]["start"].as_f64().unwrap(), c["args"]["range"]["end"].as_f64().unwrap());
+        assert!(start > 0.0 && start < 1e-300);
+        assert!(end < 1.0 && end > 0.999_999);
+    }
+
+    #[test]
+    fn tighter_bound_wins() {
+        let c = column(json!({ "type": "integer", "minimum": 5, "exclusiveMinimum": 7, "maximum": 8, "exclusiveMaximum": 100 }));
+        assert_eq!(c["args"]["range"], json!({ "start": 8, "end": 8 }));
+        let c = column(json!({ "type": "integer", "minimum": 5, "exclusiveMinimum": 2 }));
+        assert_eq!(c["args"]["range"], json!({ "start": 5, "end": 1005 }));
+    }
+
+    #[test]
+    fn unsatisfiable_bounds_skip_the_column() {
+        for property in [
+            json!({ "type": "integer", "minimum": 1.5, "maximum": 1.6 }),
+            json!({ "type": "integer", "exclusiveMinimum": 1, "exclusiveMaximum": 2 }),
+            json!({ "type": "number", "minimum": 5, "maximum": 4 }),
+            json!({ "type": "number", "exclusiveMinimum": 1, "maximum": 1 }),
+        ] {
+            let imported = import(json!({ "p": property, "q": { "type": "boolean" } }));
+            assert_eq!(imported.schema["columns"].as_array().unwrap().len(), 1, "{}", property);
+            assert!(imported.warnings[0].starts_with("p: no "), "{:?}", imported.warnings);
+        }
+        let c = column(json!({ "type": "integer", "minimum": 1, "maximum": 1 }));
+        assert_eq!(c["args"]["range"], json!({ "start": 1, "end": 1 }));
+    }
+
+    #[test]
+    fn draft4_boolean_exclusives() {
+        let c = column(json!({ "type": "integer", "minimum": 0, "exclusiveMinimum": true, "maximum": 10, "exclusiveMaximum": false }));
+        assert_eq!(c["args"]["range"], json!({ "start": 1, "end": 10 }));
+    }
+
+    #[test]
+    fn missing_bound_is_1000_from_the_other() {
+        let c = column(json!({ "type": "integer", "maximum": 0 }));
+        assert_eq!(c["args"]["range"], json!({ "start": -1000, "end": 0 }));
+        assert!(column(json!({ "type": "integer" })).get("args").is_none());
+    }
+
+    #[test]
+    fn all_of_merges_properties_and_required() {
+        let imported = schema_from_json_schema(
+            &json!({
+                "$defs": { "named": { "properties": { "name": { "type": "string" } }, "required": ["name"] } },
+                "allOf": [
+                    { "$ref": "#/$defs/named" },
+                    { "properties": { "age": { "type": "integer", "minimum": 0, "maximum": 120 } } }
+                ],
+                "properties": { "id": { "type": "integer", "minimum": 1, "maximum": 9 } }
+            }),
+            0.0,
+        )
+        .unwrap();
+        let names: Vec<&str> = imported.schema["columns"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|c| c["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, ["id", "name", "age"]);
+    }
+
+    #[test]
+    fn all_of_keeps_the_outer_keywords() {
+        let c = column(json!({ "type": "integer", "maximum": 5, "allOf": [{ "minimum": 1, "maximum": 50 }] }));
+        assert_eq!(c["args"]["range"], json!({ "start": 1, "end": 5 }));
+    }
+
+    #[test]
+    fn any_of_uses_the_first_non_null_variant() {
+        let c = column(json!({ "anyOf": [{ "type": "null" }, { "type": "string", "format": "uuid" }, { "type": "integer" }] }));
+        assert_eq!(c["type"], "UUIDv4");
+        assert_eq!(c["null_ratio"], 0.2);
+        let c = column(json!({ "oneOf": [{ "type": "integer" }, { "type": "string" }] }));
+        assert_eq!(c["type"], "i64");
+        assert!(c.get("null_ratio").is_none());
+    }
+
+    #[test]
+    fn nullable_forms_get_null_ratio() {
+        assert_eq!(column(json!({ "type": ["integer", "null"] }))["null_ratio"], 0.2);
+        let c = column(json!({ "enum": ["a", null] }));
+        assert_eq!(c["args"]["values"], json!(["a"]));
+        assert_eq!(c["null_ratio"], 0.2);
+        let imported = schema_from_json_schema(
+            &json!({ "properties": { "p": { "type": ["string", "null"] } } }),
+            0.0,
+        )
+        .unwrap();
+        assert!(imported.schema["columns"][0].get("null_ratio").is_none());
+    }
+
+    #[test]
+    fn required_properties_are_always_generated() {
+        let imported = schema_from_json_schema(
+            &json!({ "required": ["id"], "properties": { "id": { "type": "integer" }, "note": { "type": "string" } } }),
+            0.5,
+        )
+        .unwrap();
+        let columns = imported.schema["columns"].as_array().unwrap();
+        assert_eq!(columns.len(), 2);
+        assert!(columns.iter().all(|c| c.get("null_ratio").is_none()));
+    }
+
+    #[test]
+    fn max_length_with_a_format_warns_instead_of_truncating() {
+        let imported = import(json!({ "email": { "type": "string", "format": "email", "maxLength": 10 } }));
+        let c = &imported.schema["columns"][0];
+        assert_eq!(c["type"], "SafeEmail");
+        assert!(c.get("max_length").is_none());
+        assert!(imported.warnings[0].contains("maxLength isn't applied to format 'email'"));
+    }
+
+    #[test]
+    fn durations_are_non_negative_iso_8601() {
+        let c = column(json!({ "type": "string", "format": "duration", "pattern": "^x$" }));
+        assert_eq!(c["type"], "Regex");
+        assert_eq!(c["args"]["pattern"], ISO_DURATION_PATTERN);
+    }
+
+    #[test]
+    fn date_formats_stay_text() {
+        let c = column(json!({ "type": "string", "format": "date-time" }));
+        assert_eq!(c["type"], "DateTime");
+        assert_eq!(c["as_string"], true);
+    }
+
+    #[test]
+    fn recursive_refs_are_too_deep() {
+        let result = schema_from_json_schema(
+            &json!({
+                "$defs": { "node": { "type": "object", "properties": { "next": { "$ref": "#/$defs/node" } } } },
+                "properties": { "head": { "$ref": "#/$defs/node" } }
+            }),
+            0.0,
+        );
+        assert!(matches!(result, Err(JsonSchemaError::TooDeep(_))));
+        let result = schema_from_json_schema(&json!({ "properties": { "a": { "$ref": "other.json#/a" } } }), 0.0);
+        assert!(matches!(result, Err(JsonSchemaError::UnresolvedRef(_))));
+    }
+}
@@ -0,0 +1,41 @@
+//! Generate fake datasets from a JSON schema, and read, write, profile and
+//! anonymize existing ones.
+//!
+//! ```
+//! use rsfake::{Generator, Schema};
+//!
+//! let schema = Schema::from_json(r#"{
+//!     "columns": [
+//!         { "name": "id", "type": "u32", "args": { "range": { "start": 1, "end": 1000 } } },
+//!         { "name": "email", "type": "SafeEmail", "null_ratio": 0.1 }
+//!     ]
+//! }"#)?;
+//! let df = Generator::new(schema).seed(7).rows(500).generate()?;
+//! assert_eq!(df.get_column_names(), ["id", "email"]);
+//! # Ok::<(), rsfake::GenerateError>(())
+//! ```
+
+pub mod anonymize;
+pub mod auto;
+pub mod bench;
+mod builtins;
+mod compose;
+pub mod compress;
+pub mod ddl;
+pub mod describe;
+pub mod extract;
+pub mod generate;
+pub mod infer;
+pub mod jsonschema;
+pub mod mask;
+pub mod metadata;
+pub mod registry;
+pub mod schema;
+
+pub use extract::{read_file, read_stdin, write_dataframe, CsvOptions, ExtractError, OutputOptions, ParquetOptions};
+pub use generate::{Batches, GenerateError, Generator};
+pub use registry::{ColumnGenerator, Registry};
+pub use schema::{FakeSchema, Schema};
+
+#[cfg(feature = "derive")]
+pub use rsfake_derive::FakeSchema;
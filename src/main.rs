@@ -1,13 +1,23 @@
 use std::path::Path;
 use std::time::Instant;
 
-use clap::{builder::{styling::AnsiColor, Styles}, Parser};
+use clap::{builder::{styling::AnsiColor, Styles}, Args, Parser, Subcommand};
+use polars::prelude::DataFrame;
 
-mod extract;
-mod generate;
-
-use extract::{read_file, write_dataframe};
-use generate::generate_from_json;
+use rsfake::anonymize::anonymize_dataframe;
+use rsfake::bench::run_benchmark;
+use rsfake::compress::Compression;
+use rsfake::ddl::{schemas_from_sql, SqlDialect};
+use rsfake::describe::{describe_dataframe, render_report, ReportFormat, DEFAULT_TOP_K};
+use rsfake::extract::{
+    check_output, is_stream_format, listed_outputs, read_file, read_stdin, resolve_output_format, write_dataframe, CsvOptions,
+    CsvQuoteStyle, ExtractError, OutputOptions, ParquetCodec, ParquetOptions, STDIO_PATH,
+};
+use rsfake::schema::SchemaFormat;
+use rsfake::{Generator, Schema};
+use rsfake::infer::infer_schema;
+use rsfake::jsonschema::schema_from_json_schema;
+use rsfake::metadata::schema_from_file;
 
 const V3_STYLES: Styles = Styles::styled()
     .header(AnsiColor::Yellow.on_default())
@@ -19,25 +29,391 @@ const V3_STYLES: Styles = Styles::styled()
 #[command(name = "rsfake")]
 #[command(styles=V3_STYLES)]
 #[command(about = "Generates fake data based on the provided schema file.")]
-#[command(long_about = "This program generates fake data based on a JSON schema file. You can specify the number of rows, the number of threads for parallel processing, and the schema file to be used.")]
+#[command(long_about = "This program generates fake data based on a JSON, YAML or TOML schema file. You can specify the number of rows, the number of threads for parallel processing, and the schema file to be used.")]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Schema file: YAML for .yaml/.yml, TOML for .toml, JSON otherwise
     #[arg(short, long, env = "FAKER_SCHEMA_FILE", default_value = "schema.json")]
     schema: String,
 
     #[arg(short, long, env = "FAKER_NUM_ROWS", default_value = "10000")]
     rows: usize,
 
-    #[arg(short, long, env = "RAYON_NUM_THREADS", default_value = "1")]
+    /// Seed for reproducible output: the same schema, seed and row count always generate the same data
+    #[arg(long, env = "FAKER_SEED")]
+    seed: Option<u64>,
+
+    #[arg(short, long, env = "RAYON_NUM_THREADS", default_value = "1", global = true)]
     threads: usize,
 
+    /// Output path, or `-` to write csv, tsv, json, jsonl or ipc_stream to stdout.
+    /// Repeat to write the same data to several outputs
     #[arg(short, long, env = "FAKER_OUTPUT_PATH")]
-    output: Option<String>,
+    output: Vec<String>,
 
+    /// Input path, directory or glob, or `-` to read from stdin
     #[arg(short, long, env = "FAKER_INPUT_PATH")]
     input: Option<String>,
 
-    #[arg(short, long, default_value = "parquet")]
-    format: String,
+    /// Output format, inferred from the output extension when omitted (default: parquet).
+    /// With several outputs, give one format per output in the same order
+    #[arg(short, long)]
+    format: Vec<String>,
+
+    /// Write with --format even if it contradicts the output extension
+    #[arg(long)]
+    force: bool,
+
+    /// Format of data read from stdin with `-i -`
+    #[arg(long, default_value = "csv")]
+    input_format: String,
+
+    /// Print a preview of the data to stderr
+    #[arg(short, long)]
+    verbose: bool,
+
+    /// Compress csv/json output with gzip, zstd, bzip2 or xz (inferred from a .gz/.zst/.bz2/.xz output extension)
+    #[arg(long)]
+    compression: Option<Compression>,
+
+    /// Pick a type from the name of each column whose type is missing or `auto`, printing the choices to stderr
+    #[arg(long)]
+    auto_types: bool,
+
+    /// Print per-column statistics of the data as `text` or `json`; goes to stderr when data is written to stdout
+    #[arg(long, num_args = 0..=1, default_missing_value = "text", value_name = "FORMAT")]
+    report: Option<ReportFormat>,
+
+    /// With --input, replace the columns listed in the schema with consistent pseudonyms
+    #[arg(long, requires = "input")]
+    anonymize: bool,
+
+    /// Secret key for --anonymize; the same key always maps a value to the same fake
+    #[arg(long, env = "FAKER_ANONYMIZE_KEY", hide_env_values = true)]
+    anonymize_key: Option<String>,
+
+    #[command(flatten)]
+    csv: CsvArgs,
+
+    #[command(flatten)]
+    parquet: ParquetArgs,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Infer a schema from an existing dataset and print it as JSON
+    Infer {
+        /// Input path, directory or glob, or `-` to read from stdin
+        #[arg(short, long)]
+        input: String,
+
+        /// Format of data read from stdin with `-i -`
+        #[arg(long, default_value = "csv")]
+        input_format: String,
+
+        #[command(flatten)]
+        csv: CsvArgs,
+    },
+    /// Generate a schema at increasing row counts and print throughput and peak memory as JSON
+    Bench {
+        #[arg(short, long, env = "FAKER_SCHEMA_FILE", default_value = "schema.json")]
+        schema: String,
+
+        /// Comma-separated row counts to generate
+        #[arg(short, long, value_delimiter = ',', default_value = "10000,100000,1000000")]
+        rows: Vec<usize>,
+    },
+    /// Print per-column statistics for an existing dataset
+    Describe {
+        /// Input path, directory or glob, or `-` to read from stdin
+        #[arg(short, long)]
+        input: String,
+
+        /// Format of data read from stdin with `-i -`
+        #[arg(long, default_value = "csv")]
+        input_format: String,
+
+        /// Report format: `text` or `json`
+        #[arg(short, long, default_value = "text")]
+        format: ReportFormat,
+
+        /// Number of most frequent values listed per column
+        #[arg(long, default_value_t = DEFAULT_TOP_K)]
+        top_k: usize,
+
+        #[command(flatten)]
+        csv: CsvArgs,
+    },
+    /// Translate a schema file between JSON, YAML and TOML
+    ConvertSchema {
+        /// Schema file to convert, read according to its extension
+        input: String,
+
+        /// Write the converted schema here instead of stdout
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// Target format: json, yaml or toml. Inferred from the --output extension when omitted
+        #[arg(long)]
+        to: Option<SchemaFormat>,
+    },
+    /// Create schemas from other descriptions of the data
+    Schema {
+        #[command(subcommand)]
+        command: SchemaCommand,
+    },
+}
+
+// The variant names are the subcommand names, which all read `from-...`.
+#[allow(clippy::enum_variant_names)]
+#[derive(Subcommand)]
+enum SchemaCommand {
+    /// Create schemas from SQL CREATE TABLE statements
+    FromSql {
+        /// File of SQL DDL
+        input: String,
+
+        /// SQL dialect: generic, postgres, mysql or sqlite
+        #[arg(long, default_value = "generic")]
+        dialect: SqlDialect,
+
+        /// Only import this table
+        #[arg(long)]
+        table: Option<String>,
+
+        /// null_ratio given to nullable columns
+        #[arg(long, default_value_t = 0.0)]
+        null_ratio: f64,
+
+        /// Output file, or a directory receiving one schema per table when several are imported
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// Output format: json, yaml or toml. Inferred from the --output extension, defaulting to json
+        #[arg(long)]
+        to: Option<SchemaFormat>,
+    },
+
+    /// Create a schema from a JSON Schema (draft 2020-12) document
+    FromJsonSchema {
+        /// JSON Schema file, in JSON or YAML
+        input: String,
+
+        /// null_ratio given to properties that allow null
+        #[arg(long, default_value_t = 0.0)]
+        null_ratio: f64,
+
+        /// Output file
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// Output format: json, yaml or toml. Inferred from the --output extension, defaulting to json
+        #[arg(long)]
+        to: Option<SchemaFormat>,
+    },
+
+    /// Create a schema from the column types in a Parquet, Arrow IPC or Avro file, without reading its rows
+    FromFile {
+        /// Parquet, Arrow IPC (.arrow, .feather, .ipc, .arrows) or Avro file
+        input: String,
+
+        /// null_ratio given to nullable columns
+        #[arg(long, default_value_t = 0.0)]
+        null_ratio: f64,
+
+        /// Output file
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// Output format: json, yaml or toml. Inferred from the --output extension, defaulting to json
+        #[arg(long)]
+        to: Option<SchemaFormat>,
+    },
+}
+
+/// CSV dialect overrides; these take precedence over the schema's `output.csv` section.
+#[derive(Args)]
+#[command(next_help_heading = "CSV options")]
+struct CsvArgs {
+    /// Field delimiter, e.g. ',', '|' or '\t'
+    #[arg(long, value_parser = parse_char)]
+    delimiter: Option<char>,
+
+    /// Quote character
+    #[arg(long, value_parser = parse_char)]
+    quote_char: Option<char>,
+
+    /// Quoting behaviour when writing: necessary, always, non_numeric or never
+    #[arg(long)]
+    quote_style: Option<CsvQuoteStyle>,
+
+    /// Treat the first line as data when reading and omit the header when writing
+    #[arg(long)]
+    no_header: bool,
+
+    /// String used to represent null values
+    #[arg(long)]
+    null_value: Option<String>,
+
+    /// Line terminator, e.g. '\n' or '\r\n'
+    #[arg(long, value_parser = parse_escapes)]
+    line_terminator: Option<String>,
+
+    /// chrono format string for date columns
+    #[arg(long)]
+    date_format: Option<String>,
+
+    /// chrono format string for time columns
+    #[arg(long)]
+    time_format: Option<String>,
+
+    /// chrono format string for datetime columns
+    #[arg(long)]
+    datetime_format: Option<String>,
+
+    /// Write a UTF-8 byte order mark
+    #[arg(long)]
+    bom: bool,
+}
+
+impl CsvArgs {
+    fn apply(&self, mut options: CsvOptions) -> CsvOptions {
+        if self.delimiter.is_some() {
+            options.delimiter = self.delimiter;
+        }
+        if let Some(quote_char) = self.quote_char {
+            options.quote_char = quote_char;
+        }
+        if let Some(quote_style) = self.quote_style {
+            options.quote_style = quote_style;
+        }
+        if self.no_header {
+            options.header = false;
+        }
+        if let Some(null_value) = &self.null_value {
+            options.null_value = null_value.clone();
+        }
+        if let Some(line_terminator) = &self.line_terminator {
+            options.line_terminator = line_terminator.clone();
+        }
+        if self.date_format.is_some() {
+            options.date_format = self.date_format.clone();
+        }
+        if self.time_format.is_some() {
+            options.time_format = self.time_format.clone();
+        }
+        if self.datetime_format.is_some() {
+            options.datetime_format = self.datetime_format.clone();
+        }
+        if self.bom {
+            options.bom = true;
+        }
+        options
+    }
+}
+
+/// Parquet writer overrides; these take precedence over the schema's `output.parquet` section.
+#[derive(Args)]
+#[command(next_help_heading = "Parquet options")]
+struct ParquetArgs {
+    /// Compression codec: none, snappy, gzip, lz4, brotli or zstd
+    #[arg(long)]
+    parquet_compression: Option<ParquetCodec>,
+
+    /// Compression level for gzip (0-10), brotli (0-11) or zstd (1-22)
+    #[arg(long)]
+    parquet_compression_level: Option<i32>,
+
+    /// Maximum number of rows per row group
+    #[arg(long)]
+    parquet_row_group_size: Option<usize>,
+
+    /// Target size of a data page in bytes
+    #[arg(long)]
+    parquet_data_page_size: Option<usize>,
+
+    /// Don't write column statistics
+    #[arg(long)]
+    no_parquet_statistics: bool,
+
+    /// Write all columns with plain encoding instead of dictionary encoding
+    #[arg(long)]
+    no_parquet_dictionary: bool,
+
+    /// Write a Hive-partitioned directory of files, e.g. `year,country`
+    #[arg(long, value_delimiter = ',')]
+    partition_by: Vec<String>,
+
+    /// Split the output into multiple files of at most this many rows
+    #[arg(long)]
+    max_rows_per_file: Option<usize>,
+}
+
+impl ParquetArgs {
+    fn apply(&self, mut options: ParquetOptions) -> ParquetOptions {
+        if let Some(compression) = self.parquet_compression {
+            options.compression = compression;
+        }
+        if self.parquet_compression_level.is_some() {
+            options.compression_level = self.parquet_compression_level;
+        }
+        if self.parquet_row_group_size.is_some() {
+            options.row_group_size = self.parquet_row_group_size;
+        }
+        if self.parquet_data_page_size.is_some() {
+            options.data_page_size = self.parquet_data_page_size;
+        }
+        if self.no_parquet_statistics {
+            options.statistics = false;
+        }
+        if self.no_parquet_dictionary {
+            options.dictionary = false;
+        }
+        if !self.partition_by.is_empty() {
+            options.partition_by = self.partition_by.clone();
+        }
+        if self.max_rows_per_file.is_some() {
+            options.max_rows_per_file = self.max_rows_per_file;
+        }
+        options
+    }
+}
+
+fn parse_escapes(s: &str) -> Result<String, String> {
+    Ok(s.replace("\\t", "\t").replace("\\r", "\r").replace("\\n", "\n"))
+}
+
+fn parse_char(s: &str) -> Result<char, String> {
+    let unescaped = parse_escapes(s)?;
+    let mut chars = unescaped.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Ok(c),
+        _ => Err(format!("expected a single character, got '{}'", s)),
+    }
+}
+
+/// The schema format from `--to`, else from the output file's extension.
+fn schema_format(output: Option<&str>, to: Option<SchemaFormat>) -> Option<SchemaFormat> {
+    to.or_else(|| output.and_then(SchemaFormat::from_extension))
+}
+
+fn write_schema(schema: &Schema, output: Option<&str>, format: SchemaFormat) -> Result<(), Box<dyn std::error::Error>> {
+    let text = schema.render(format)?;
+    match output {
+        Some(path) => std::fs::write(path, text)?,
+        None => print!("{}", text),
+    }
+    Ok(())
+}
+
+fn read_input(input_path: &str, input_format: &str, csv_options: &CsvOptions) -> Result<DataFrame, ExtractError> {
+    if input_path == STDIO_PATH {
+        read_stdin(input_format, csv_options)
+    } else {
+        read_file(input_path, csv_options)
+    }
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -47,36 +423,230 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .num_threads(cli.threads)
         .build_global()?;
 
+    match &cli.command {
+        Some(Command::Infer { input, input_format, csv }) => {
+            let df = read_input(input, input_format, &csv.apply(CsvOptions::default()))?;
+            println!("{}", serde_json::to_string_pretty(&infer_schema(&df)?)?);
+            return Ok(());
+        }
+        Some(Command::Describe { input, input_format, format, top_k, csv }) => {
+            let df = read_input(input, input_format, &csv.apply(CsvOptions::default()))?;
+            print!("{}", render_report(&describe_dataframe(&df, *top_k)?, *format)?);
+            return Ok(());
+        }
+        Some(Command::Bench { schema, rows }) => {
+            println!("{}", serde_json::to_string_pretty(&run_benchmark(schema, rows, cli.threads)?)?);
+            return Ok(());
+        }
+        Some(Command::ConvertSchema { input, output, to }) => {
+            let format = schema_format(output.as_deref(), *to)
+                .ok_or("--to is required unless --output ends in .json, .yaml, .yml or .toml")?;
+            write_schema(&Schema::from_path(input)?, output.as_deref(), format)?;
+            return Ok(());
+        }
+        Some(Command::Schema { command: SchemaCommand::FromSql { input, dialect, table, null_ratio, output, to } }) => {
+            let mut tables = schemas_from_sql(&std::fs::read_to_string(input)?, *dialect, *null_ratio)?;
+            if let Some(table) = table {
+                tables.retain(|t| &t.name == table);
+                if tables.is_empty() {
+                    return Err(format!("no CREATE TABLE for '{}' in {}", table, input).into());
+                }
+            }
+            for warning in tables.iter().flat_map(|t| &t.warnings) {
+                eprintln!("warning: {}", warning);
+            }
+            let format = schema_format(output.as_deref(), *to).unwrap_or(SchemaFormat::Json);
+            match (tables.as_slice(), output) {
+                ([table], _) => write_schema(&Schema::from_value(table.schema.clone())?, output.as_deref(), format)?,
+                (tables, Some(dir)) => {
+                    std::fs::create_dir_all(dir)?;
+                    let extension = to.map_or("json", SchemaFormat::extension);
+                    for table in tables {
+                        let path = Path::new(dir).join(format!("{}.{}", table.name, extension));
+                        write_schema(&Schema::from_value(table.schema.clone())?, path.to_str(), format)?;
+                    }
+                }
+                (tables, None) => {
+                    let names: Vec<&str> = tables.iter().map(|t| t.name.as_str()).collect();
+                    return Err(format!(
+                        "{} defines several tables ({}); pick one with --table or pass an --output directory",
+                        input,
+                        names.join(", ")
+                    )
+                    .into());
+                }
+            }
+            return Ok(());
+        }
+        Some(Command::Schema { command: SchemaCommand::FromJsonSchema { input, null_ratio, output, to } }) => {
+            let text = std::fs::read_to_string(input)?;
+            let document: serde_json::Value = match SchemaFormat::from_extension(input) {
+                Some(SchemaFormat::Yaml) => serde_yaml::from_str(&text)?,
+                _ => serde_json::from_str(&text)?,
+            };
+            let imported = schema_from_json_schema(&document, *null_ratio)?;
+            for warning in &imported.warnings {
+                eprintln!("warning: {}", warning);
+            }
+            let format = schema_format(output.as_deref(), *to).unwrap_or(SchemaFormat::Json);
+            write_schema(&Schema::from_value(imported.schema)?, output.as_deref(), format)?;
+            return Ok(());
+        }
+        Some(Command::Schema { command: SchemaCommand::FromFile { input, null_ratio, output, to } }) => {
+            let imported = schema_from_file(input, *null_ratio)?;
+            for warning in &imported.warnings {
+                eprintln!("warning: {}", warning);
+            }
+            let format = schema_format(output.as_deref(), *to).unwrap_or(SchemaFormat::Json);
+            write_schema(&Schema::from_value(imported.schema)?, output.as_deref(), format)?;
+            return Ok(());
+        }
+        None => {}
+    }
+
+    // Plain input mode converts data without a schema, so only load one when it's used.
+    let schema = if cli.input.is_none() || cli.anonymize {
+        let mut schema = Schema::from_path(&cli.schema)?;
+        if cli.auto_types {
+            for picked in schema.resolve_auto_types() {
+                let mut column = serde_json::json!({ "type": picked.type_name });
+                if let Some(args) = picked.args {
+                    column["args"] = args;
+                }
+                let note = if picked.matched { "" } else { " (no match in the name)" };
+                eprintln!("auto type: {} -> {}{}", picked.column, column, note);
+            }
+        }
+        Some(schema)
+    } else {
+        None
+    };
+    let output_section = schema.as_ref().and_then(|schema| schema.section("output"));
+    let listed = match output_section {
+        Some(serde_json::Value::Object(section)) => listed_outputs(section)?,
+        _ => Vec::new(),
+    };
+
+    if !cli.format.is_empty() && cli.format.len() != cli.output.len() {
+        return Err(format!(
+            "got {} --format values for {} outputs, give one per output or none",
+            cli.format.len(),
+            cli.output.len()
+        )
+        .into());
+    }
+    // Without -o, the outputs listed in the schema are written.
+    let requested: Vec<(String, Option<String>)> = if cli.output.is_empty() {
+        listed.into_iter().map(|entry| (entry.path, entry.format)).collect()
+    } else {
+        cli.output
+            .iter()
+            .enumerate()
+            .map(|(i, path)| {
+                let listed_format = listed.iter().find(|entry| &entry.path == path).and_then(|entry| entry.format.clone());
+                (path.clone(), cli.format.get(i).cloned().or(listed_format))
+            })
+            .collect()
+    };
+    if requested.iter().filter(|(path, _)| path == STDIO_PATH).count() > 1 {
+        return Err("stdout can only be used for one output".into());
+    }
+    let outputs = requested
+        .into_iter()
+        .map(|(path, format)| {
+            let format = resolve_output_format(&path, format.as_deref(), cli.force)?;
+            let mut options = OutputOptions::for_output(output_section, &path, &format)?;
+            if cli.compression.is_some() && is_stream_format(&format) {
+                options.compression = cli.compression;
+            }
+            options.csv = cli.csv.apply(options.csv);
+            options.parquet = cli.parquet.apply(options.parquet);
+            // Fail before generating anything if the output can't be written.
+            check_output(&path, &format, &options)?;
+            Ok((path, format, options))
+        })
+        .collect::<Result<Vec<_>, ExtractError>>()?;
+    if cli.compression.is_some() && !outputs.iter().any(|(_, format, _)| is_stream_format(format)) {
+        return Err("--compression only applies to csv, tsv, json, jsonl and ipc_stream outputs".into());
+    }
+    let show_preview = cli.verbose || outputs.is_empty();
+
     let mut df = if let Some(input_path) = cli.input {
         let start_time = Instant::now();
-        let df = read_file(&input_path)?;
+        let mut df = read_input(&input_path, &cli.input_format, &cli.csv.apply(CsvOptions::default()))?;
+        if let Some(schema) = &schema {
+            let key = cli.anonymize_key.as_deref().ok_or("--anonymize requires --anonymize-key or FAKER_ANONYMIZE_KEY")?;
+            df = anonymize_dataframe(df, schema, key.as_bytes())?;
+        }
+        let source = if input_path == STDIO_PATH {
+            cli.input_format.as_str()
+        } else {
+            Path::new(&input_path).extension().unwrap_or_default().to_str().unwrap_or("unknown")
+        };
         let elapsed = start_time.elapsed().as_secs_f64();
-        println!("{:?}", df);
-        println!(
-            "Time taken to read from {}: {:.3} seconds",
-            Path::new(&input_path).extension().unwrap_or_default().to_str().unwrap_or("unknown"),
-            elapsed
-        );
+        if show_preview {
+            eprintln!("{:?}", df);
+        }
+        eprintln!("Time taken to read from {}: {:.3} seconds", source, elapsed);
         df
     } else {
         let start_time = Instant::now();
-        let df = generate_from_json(&cli.schema, cli.rows)?;
+        let mut generator = Generator::new(schema.expect("generate mode always loads a schema")).rows(cli.rows);
+        if let Some(seed) = cli.seed {
+            generator = generator.seed(seed);
+        }
+        let df = generator.generate()?;
         let elapsed = start_time.elapsed().as_secs_f64();
-        println!("{:?}", df);
-        println!(
+        if show_preview {
+            eprintln!("{:?}", df);
+        }
+        eprintln!(
             "Time taken to generate {} rows into a dataframe using {} threads: {:.3} seconds",
             cli.rows, cli.threads, elapsed
         );
         df
     };
 
-    if let Some(output_path) = cli.output {
-        let start_time = Instant::now();
-        write_dataframe(&mut df, &output_path, &cli.format)?;
-        let elapsed = start_time.elapsed().as_secs_f64();
-        println!(
-            "Time taken to write to {}: {:.3} seconds",
-            cli.format, elapsed
+    if let Some(format) = cli.report {
+        let report = render_report(&describe_dataframe(&df, DEFAULT_TOP_K)?, format)?;
+        if outputs.iter().any(|(path, _, _)| path == STDIO_PATH) {
+            eprint!("{}", report);
+        } else {
+            print!("{}", report);
+        }
+    }
+
+    // Rechunk once up front so each output can write from a cheap clone, each on its own thread.
+    df.as_single_chunk_par();
+    let start_time = Instant::now();
+    let timings = std::thread::scope(|scope| {
+        let writers: Vec<_> = outputs
+            .iter()
+            .map(|(output_path, format, options)| {
+                let df = &df;
+                scope.spawn(move || {
+                    let start_time = Instant::now();
+                    write_dataframe(&mut df.clone(), output_path, format, options)?;
+                    Ok((output_path, format, start_time.elapsed().as_secs_f64()))
+                })
+            })
+            .collect();
+        writers
+            .into_iter()
+            .map(|writer| writer.join().expect("output writer panicked"))
+            .collect::<Result<Vec<_>, ExtractError>>()
+    })?;
+    for (output_path, format, elapsed) in timings {
+        eprintln!(
+            "Time taken to write {} to {}: {:.3} seconds",
+            format, output_path, elapsed
+        );
+    }
+    if outputs.len() > 1 {
+        eprintln!(
+            "Time taken to write {} outputs: {:.3} seconds",
+            outputs.len(),
+            start_time.elapsed().as_secs_f64()
         );
     }
 
@@ -0,0 +1,422 @@
+use std::collections::HashMap;
+
+use fake::faker::internet::raw::Username;
+use fake::locales::EN;
+use fake::Fake;
+use polars::prelude::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde_json::Value;
+use thiserror::Error;
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, SecondsFormat};
+
+use crate::anonymize::keyed_digest;
+
+#[derive(Error, Debug)]
+pub enum MaskError {
+    #[error("Polars error: {0}")]
+    Polars(#[from] polars::error::PolarsError),
+    #[error("Unsupported mask: {0}")]
+    UnsupportedMask(String),
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(String),
+}
+
+/// Applies the named masking transform to `series`, keeping the shape of each
+/// value. `df` is the unmodified input, used to look up `date_shift` entities.
+pub fn mask_series(df: &DataFrame, series: &Series, mask: &str, col_def: &Value, key: &[u8]) -> Result<Series, MaskError> {
+    let args = col_def.get("args");
+    match mask {
+        "redact" => redact(series, args),
+        "keep_domain" => keep_domain(series, key),
+        "date_shift" => date_shift(df, series, args, key),
+        "noise" => noise(series, args, key),
+        "hash" => hash(series, args, key),
+        _ => Err(MaskError::UnsupportedMask(mask.to_string())),
+    }
+}
+
+/// Replaces all but the first `keep_first` and last `keep_last` characters
+/// with `char`, e.g. `****1234`. `mask_length` fixes the number of mask
+/// characters instead of matching the value's length.
+fn redact(series: &Series, args: Option<&Value>) -> Result<Series, MaskError> {
+    let keep_first = usize_arg(args, "keep_first")?.unwrap_or(0);
+    let keep_last = usize_arg(args, "keep_last")?.unwrap_or(4);
+    let mask_length = usize_arg(args, "mask_length")?;
+    let mask_char = match str_arg(args, "char") {
+        Some(c) => {
+            let mut chars = c.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => c,
+                _ => return Err(MaskError::InvalidArgument("'char' must be a single character".to_string())),
+            }
+        }
+        None => '*',
+    };
+
+    map_strings(series, |value| {
+        let chars: Vec<char> = value.chars().collect();
+        // Too short to keep anything without revealing the whole value.
+        if chars.len() <= keep_first + keep_last {
+            return std::iter::repeat_n(mask_char, mask_length.unwrap_or(chars.len())).collect();
+        }
+        let masked = mask_length.unwrap_or(chars.len() - keep_first - keep_last);
+        chars[..keep_first]
+            .iter()
+            .copied()
+            .chain(std::iter::repeat_n(mask_char, masked))
+            .chain(chars[chars.len() - keep_last..].iter().copied())
+            .collect()
+    })
+}
+
+/// Replaces the local part of an email with a consistent fake username,
+/// keeping the domain.
+fn keep_domain(series: &Series, key: &[u8]) -> Result<Series, MaskError> {
+    map_strings(series, |value| {
+        let mut rng = StdRng::from_seed(keyed_digest(key, value));
+        let local: String = Username(EN).fake_with_rng(&mut rng);
+        match value.rsplit_once('@') {
+            Some((_, domain)) => format!("{}@{}", local, domain),
+            None => local,
+        }
+    })
+}
+
+/// Shifts dates by a whole number of days in `±max_days`. The offset is fixed
+/// per value of the `entity` column, so intervals within an entity survive;
+/// without an entity every row shifts by the same offset.
+fn date_shift(df: &DataFrame, series: &Series, args: Option<&Value>, key: &[u8]) -> Result<Series, MaskError> {
+    let max_days = usize_arg(args, "max_days")?.unwrap_or(30) as i64;
+    let entities = match str_arg(args, "entity") {
+        Some(name) => Some(
+            df.column(name)
+                .map_err(|_| MaskError::InvalidArgument(format!("entity column '{}' not found", name)))?
+                .cast(&DataType::String)?,
+        ),
+        None => None,
+    };
+
+    let offset_for = |entity: &str| -> i64 {
+        let mut rng = StdRng::from_seed(keyed_digest(key, &format!("date_shift:{}", entity)));
+        rng.gen_range(-max_days..=max_days)
+    };
+    let days: Vec<i64> = match &entities {
+        Some(entities) => {
+            let mut cache: HashMap<&str, i64> = HashMap::new();
+            entities
+                .str()?
+                .into_iter()
+                .map(|entity| {
+                    let entity = entity.unwrap_or_default();
+                    *cache.entry(entity).or_insert_with(|| offset_for(entity))
+                })
+                .collect()
+        }
+        None => vec![offset_for(""); series.len()],
+    };
+
+    let per_day: i64 = match series.dtype() {
+        DataType::Date => 1,
+        DataType::Datetime(TimeUnit::Milliseconds, _) => 86_400_000,
+        DataType::Datetime(TimeUnit::Microseconds, _) => 86_400_000_000,
+        DataType::Datetime(TimeUnit::Nanoseconds, _) => 86_400_000_000_000,
+        #[cfg(feature = "chrono")]
+        DataType::String => return shift_date_strings(series, &days),
+        other => {
+            return Err(MaskError::InvalidArgument(format!(
+                "'date_shift' needs a date, datetime or string column, got {}",
+                other
+            )))
+        }
+    };
+    let ticks = series.cast(&DataType::Int64)?;
+    let shifted: Int64Chunked = ticks
+        .i64()?
+        .into_iter()
+        .zip(days)
+        .map(|(ticks, days)| ticks.map(|t| t + days * per_day))
+        .collect();
+    Ok(shifted.into_series().cast(series.dtype())?.with_name(series.name()))
+}
+
+/// Shifts dates stored as text, writing each back in the format it was read in.
+#[cfg(feature = "chrono")]
+fn shift_date_strings(series: &Series, days: &[i64]) -> Result<Series, MaskError> {
+    const NAIVE_FORMATS: [&str; 2] = ["%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%dT%H:%M:%S%.f"];
+
+    let shifted = series
+        .str()?
+        .into_iter()
+        .zip(days)
+        .map(|(value, days)| {
+            let Some(value) = value else {
+                return Ok(None);
+            };
+            let offset = Duration::days(*days);
+            if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+                return Ok(Some((date + offset).format("%Y-%m-%d").to_string()));
+            }
+            if let Ok(datetime) = DateTime::parse_from_rfc3339(value) {
+                return Ok(Some((datetime + offset).to_rfc3339_opts(SecondsFormat::AutoSi, value.ends_with('Z'))));
+            }
+            for format in NAIVE_FORMATS {
+                if let Ok(datetime) = NaiveDateTime::parse_from_str(value, format) {
+                    return Ok(Some((datetime + offset).format(format).to_string()));
+                }
+            }
+            Err(MaskError::InvalidArgument(format!("'date_shift' can't parse '{}' as a date", value)))
+        })
+        .collect::<Result<StringChunked, MaskError>>()?;
+    Ok(shifted.with_name(series.name()).into_series())
+}
+
+/// Scales each number by a random factor within `±percent`%. Equal values get
+/// equal noise, so repeated extracts can't be averaged back to the original.
+/// Results are clamped to the column's dtype, so they never overflow to null.
+fn noise(series: &Series, args: Option<&Value>, key: &[u8]) -> Result<Series, MaskError> {
+    let percent = args
+        .and_then(|a| a.get("percent"))
+        .and_then(Value::as_f64)
+        .filter(|p| *p >= 0.0)
+        .ok_or_else(|| MaskError::InvalidArgument("'noise' needs a non-negative 'percent' argument".to_string()))?;
+    let dtype = series.dtype();
+    if !dtype.is_numeric() {
+        return Err(MaskError::InvalidArgument(format!("'noise' needs a numeric column, got {}", dtype)));
+    }
+
+    let (min, max) = numeric_bounds(dtype);
+    let values = series.cast(&DataType::Float64)?;
+    let noisy: Float64Chunked = values
+        .f64()?
+        .into_iter()
+        .map(|value| {
+            value.map(|v| {
+                let mut rng = StdRng::from_seed(keyed_digest(key, &format!("noise:{}", v)));
+                let noisy = (v * (1.0 + rng.gen_range(-percent..=percent) / 100.0)).clamp(min, max);
+                if dtype.is_integer() { noisy.round() } else { noisy }
+            })
+        })
+        .collect();
+    Ok(noisy.into_series().cast(dtype)?.with_name(series.name()))
+}
+
+/// The range of values `dtype` can hold, as `f64`. The 64-bit maxima round up
+/// to a power of two when converted, so they step down to the next float.
+fn numeric_bounds(dtype: &DataType) -> (f64, f64) {
+    match dtype {
+        DataType::Int8 => (i8::MIN as f64, i8::MAX as f64),
+        DataType::Int16 => (i16::MIN as f64, i16::MAX as f64),
+        DataType::Int32 => (i32::MIN as f64, i32::MAX as f64),
+        DataType::Int64 => (i64::MIN as f64, (i64::MAX as f64).next_down()),
+        DataType::UInt8 => (0.0, u8::MAX as f64),
+        DataType::UInt16 => (0.0, u16::MAX as f64),
+        DataType::UInt32 => (0.0, u32::MAX as f64),
+        DataType::UInt64 => (0.0, (u64::MAX as f64).next_down()),
+        DataType::Float32 => (f32::MIN as f64, f32::MAX as f64),
+        _ => (f64::MIN, f64::MAX),
+    }
+}
+
+/// Replaces values with their hex HMAC-SHA256 under `salt`, or under the
+/// anonymize key when no salt is given, optionally truncated to `length`.
+fn hash(series: &Series, args: Option<&Value>, key: &[u8]) -> Result<Series, MaskError> {
+    let salt = str_arg(args, "salt").map(str::as_bytes).unwrap_or(key);
+    let length = usize_arg(args, "length")?;
+    map_strings(series, |value| {
+        let mut hex: String = keyed_digest(salt, value).iter().map(|b| format!("{:02x}", b)).collect();
+        if let Some(length) = length {
+            hex.truncate(length);
+        }
+        hex
+    })
+}
+
+fn map_strings<F: Fn(&str) -> String>(series: &Series, f: F) -> Result<Series, MaskError> {
+    let values = series.cast(&DataType::String)?;
+    let masked: StringChunked = values.str()?.into_iter().map(|v| v.map(&f)).collect();
+    Ok(masked.with_name(series.name()).into_series())
+}
+
+fn str_arg<'a>(args: Option<&'a Value>, key: &str) -> Option<&'a str> {
+    args.and_then(|a| a.get(key)).and_then(Value::as_str)
+}
+
+fn usize_arg(args: Option<&Value>, key: &str) -> Result<Option<usize>, MaskError> {
+    args.and_then(|a| a.get(key))
+        .map(|v| {
+            v.as_u64()
+                .map(|n| n as usize)
+                .ok_or_else(|| MaskError::InvalidArgument(format!("'{}' must be a non-negative integer", key)))
+        })
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const KEY: &[u8] = b"test key";
+
+    #[test]
+    fn noise_stays_within_unsigned_range() {
+        let series = Series::new("n", &[0u8, 1, 128, 254, 255]);
+        let col_def = json!({"args": {"percent": 50}});
+        let masked = mask_series(&DataFrame::empty(), &series, "noise", &col_def, KEY).unwrap();
+        assert_eq!(masked.dtype(), &DataType::UInt8);
+        assert_eq!(masked.null_count(), 0);
+    }
+
+    #[test]
+    fn noise_stays_within_signed_range() {
+        let series = Series::new("n", &[i8::MIN, -1, 0, i8::MAX]);
+        let col_def = json!({"args": {"percent": 100}});
+        let masked = mask_series(&DataFrame::empty(), &series, "noise", &col_def, KEY).unwrap();
+        assert_eq!(masked.null_count(), 0);
+        let series = Series::new("n", &[i64::MAX, i64::MIN]);
+        let masked = mask_series(&DataFrame::empty(), &series, "noise", &col_def, KEY).unwrap();
+        assert_eq!(masked.null_count(), 0);
+    }
+
+    #[test]
+    fn noise_keeps_nulls_and_is_deterministic() {
+        let series = Series::new("n", &[Some(10.0), None, Some(10.0)]);
+        let col_def = json!({"args": {"percent": 10}});
+        let masked = mask_series(&DataFrame::empty(), &series, "noise", &col_def, KEY).unwrap();
+        let values: Vec<Option<f64>> = masked.f64().unwrap().into_iter().collect();
+        assert_eq!(values[1], None);
+        assert_eq!(values[0], values[2]);
+        assert!((9.0..=11.0).contains(&values[0].unwrap()));
+    }
+
+    #[test]
+    fn noise_rejects_strings() {
+        let series = Series::new("n", &["a"]);
+        let col_def = json!({"args": {"percent": 10}});
+        assert!(mask_series(&DataFrame::empty(), &series, "noise", &col_def, KEY).is_err());
+    }
+
+    fn masked(values: &[&str], mask: &str, col_def: Value) -> Vec<String> {
+        let series = Series::new("s", values);
+        let masked = mask_series(&DataFrame::empty(), &series, mask, &col_def, KEY).unwrap();
+        masked.str().unwrap().into_iter().map(|v| v.unwrap().to_string()).collect()
+    }
+
+    #[test]
+    fn redact_keeps_the_last_four_by_default() {
+        assert_eq!(masked(&["4111111111111111"], "redact", json!({})), ["************1111"]);
+    }
+
+    #[test]
+    fn redact_keeps_first_and_last() {
+        let col_def = json!({"args": {"keep_first": 2, "keep_last": 3, "char": "#"}});
+        assert_eq!(masked(&["abcdefgh"], "redact", col_def), ["ab###fgh"]);
+    }
+
+    #[test]
+    fn redact_mask_length_hides_the_value_length() {
+        let col_def = json!({"args": {"keep_first": 1, "keep_last": 1, "mask_length": 3}});
+        assert_eq!(masked(&["ab12345yz", "abc"], "redact", col_def), ["a***z", "a***c"]);
+    }
+
+    #[test]
+    fn redact_masks_short_values_entirely() {
+        let col_def = json!({"args": {"keep_first": 2, "keep_last": 2}});
+        assert_eq!(masked(&["abcd", "ab", ""], "redact", col_def), ["****", "**", ""]);
+        let col_def = json!({"args": {"keep_first": 2, "keep_last": 2, "mask_length": 6}});
+        assert_eq!(masked(&["abc"], "redact", col_def), ["******"]);
+    }
+
+    #[test]
+    fn redact_rejects_invalid_arguments() {
+        let series = Series::new("s", &["abc"]);
+        for col_def in [json!({"args": {"char": "**"}}), json!({"args": {"keep_last": -1}})] {
+            assert!(mask_series(&DataFrame::empty(), &series, "redact", &col_def, KEY).is_err());
+        }
+    }
+
+    #[test]
+    fn keep_domain_keeps_the_domain_and_a_consistent_local_part() {
+        let values = masked(&["ann@example.com", "bob@test.org", "ann@example.com", "no-at-sign"], "keep_domain", json!({}));
+        let (local, domain) = values[0].rsplit_once('@').unwrap();
+        assert_eq!(domain, "example.com");
+        assert_ne!(local, "ann");
+        assert!(values[1].ends_with("@test.org"));
+        assert_eq!(values[0], values[2]);
+        assert!(!values[3].contains('@'));
+    }
+
+    #[test]
+    fn hash_is_keyed_and_salted() {
+        let values = masked(&["a", "b", "a"], "hash", json!({}));
+        assert_eq!(values[0], values[2]);
+        assert_ne!(values[0], values[1]);
+        assert_eq!(values[0].len(), 64);
+        assert!(values[0].chars().all(|c| c.is_ascii_hexdigit()));
+
+        let salted = masked(&["a"], "hash", json!({"args": {"salt": "pepper"}}));
+        assert_ne!(salted[0], values[0]);
+        // A salt replaces the key, so hashes match across runs and keys.
+        let series = Series::new("s", &["a"]);
+        let col_def = json!({"args": {"salt": "pepper"}});
+        let other_key = mask_series(&DataFrame::empty(), &series, "hash", &col_def, b"other key").unwrap();
+        assert_eq!(other_key.str().unwrap().get(0), Some(salted[0].as_str()));
+    }
+
+    #[test]
+    fn hash_length_truncates() {
+        let full = masked(&["a"], "hash", json!({}));
+        let short = masked(&["a"], "hash", json!({"args": {"length": 12}}));
+        assert_eq!(short[0], full[0][..12]);
+    }
+
+    fn shifted_days(df: &DataFrame, col_def: &Value) -> Vec<i32> {
+        let masked = mask_series(df, df.column("day").unwrap(), "date_shift", col_def, KEY).unwrap();
+        let original = df.column("day").unwrap().cast(&DataType::Int32).unwrap();
+        let masked = masked.cast(&DataType::Int32).unwrap();
+        original
+            .i32()
+            .unwrap()
+            .into_iter()
+            .zip(masked.i32().unwrap())
+            .map(|(a, b)| b.unwrap() - a.unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn date_shift_is_consistent_per_entity() {
+        let day = Series::new("day", &[100i32, 200, 300, 400]).cast(&DataType::Date).unwrap();
+        let patient = Series::new("patient", &["a", "b", "a", "b"]);
+        let df = DataFrame::new(vec![day, patient]).unwrap();
+        let col_def = json!({"args": {"entity": "patient", "max_days": 1000}});
+        let offsets = shifted_days(&df, &col_def);
+        assert_eq!(offsets[0], offsets[2]);
+        assert_eq!(offsets[1], offsets[3]);
+        assert!(offsets.iter().all(|d| d.abs() <= 1000));
+        assert_eq!(offsets, shifted_days(&df, &col_def));
+    }
+
+    #[test]
+    fn date_shift_without_entity_shifts_every_row_equally() {
+        let day = Series::new("day", &[1i32, 50, 9000]).cast(&DataType::Date).unwrap();
+        let df = DataFrame::new(vec![day]).unwrap();
+        let offsets = shifted_days(&df, &json!({"args": {"max_days": 30}}));
+        assert!(offsets.iter().all(|d| *d == offsets[0] && d.abs() <= 30));
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn date_shift_strings_keep_their_format() {
+        let day = Series::new("day", &["2024-01-31", "2024-01-31T10:00:00Z", "2024-01-31 10:00:00"]);
+        let df = DataFrame::new(vec![day.clone()]).unwrap();
+        let masked = mask_series(&df, &day, "date_shift", &json!({"args": {"max_days": 10}}), KEY).unwrap();
+        let values: Vec<&str> = masked.str().unwrap().into_iter().map(Option::unwrap).collect();
+        assert_eq!(values[0].len(), 10);
+        assert!(values[1].ends_with('Z') && values[1].contains('T'));
+        assert_eq!(&values[2][10..], " 10:00:00");
+        assert_eq!(values[0], &values[1][..10]);
+    }
+}
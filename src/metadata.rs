@@ -0,0 +1,153 @@
+use std::fs::File;
+use std::path::Path;
+
+use polars::io::avro::AvroReader;
+use polars::prelude::*;
+use serde_json::{json, Map, Value};
+use thiserror::Error;
+
+use crate::ddl::decimal_type;
+
+#[derive(Error, Debug)]
+pub enum MetadataError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Polars error: {0}")]
+    Polars(#[from] PolarsError),
+    #[error("Can't read a schema from '{0}'; expected a .parquet, .arrow, .feather, .ipc, .arrows or .avro file")]
+    UnsupportedFile(String),
+}
+
+/// The schema imported from a file's metadata.
+#[derive(Debug)]
+pub struct FileSchema {
+    pub schema: Value,
+    /// Columns whose type had no close equivalent and fell back to `Word`.
+    pub warnings: Vec<String>,
+}
+
+/// Builds a schema from the column types stored in a Parquet, Arrow IPC or
+/// Avro file's metadata, without reading any rows. Integer, float, boolean,
+/// decimal, date, time and timestamp columns keep their dtype, lists and
+/// structs become `Array` and `Object` columns, and durations use the text
+/// `Duration` generator. Nullable columns get
+/// `null_ratio` when it's above zero. Use [`crate::infer::infer_schema`] on
+/// the data instead to also pick up value ranges and patterns.
+pub fn schema_from_file(path: &str, null_ratio: f64) -> Result<FileSchema, MetadataError> {
+    let extension = Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_ascii_lowercase)
+        .unwrap_or_default();
+    let fields = match extension.as_str() {
+        "parquet" | "pq" => ParquetReader::new(File::open(path)?).schema()?.fields.clone(),
+        "arrow" | "feather" | "ipc" => IpcReader::new(File::open(path)?).schema()?.fields.clone(),
+        "arrows" => IpcStreamReader::new(File::open(path)?).arrow_schema()?.fields,
+        "avro" => AvroReader::new(File::open(path)?).arrow_schema()?.fields,
+        _ => return Err(MetadataError::UnsupportedFile(path.to_string())),
+    };
+    Ok(schema_from_fields(&fields, null_ratio))
+}
+
+/// Builds a schema from Arrow fields; see [`schema_from_file`].
+///
+/// ```
+/// use polars::prelude::{ArrowDataType, ArrowField};
+/// use rsfake::metadata::schema_from_fields;
+///
+/// let imported = schema_from_fields(
+///     &[
+///         ArrowField::new("id", ArrowDataType::Int16, false),
+///         ArrowField::new("price", ArrowDataType::Decimal(7, 2), true),
+///     ],
+///     0.1,
+/// );
+/// let columns = &imported.schema["columns"];
+/// assert_eq!(columns[0]["type"], "i16");
+/// assert_eq!(columns[1]["type"], "decimal128");
+/// assert_eq!(columns[1]["args"]["precision"], 7);
+/// assert_eq!(columns[1]["null_ratio"], 0.1);
+/// ```
+pub fn schema_from_fields(fields: &[ArrowField], null_ratio: f64) -> FileSchema {
+    let mut warnings = Vec::new();
+    let columns: Vec<Value> = fields
+        .iter()
+        .filter_map(|field| import_field(field, &field.name, null_ratio, &mut warnings))
+        .collect();
+    FileSchema { schema: json!({ "columns": columns }), warnings }
+}
+
+/// The column for one field, or `None` if it can't be generated.
+fn import_field(field: &ArrowField, path: &str, null_ratio: f64, warnings: &mut Vec<String>) -> Option<Value> {
+    let mut def = Map::new();
+    def.insert("name".to_string(), json!(field.name));
+    let mut set = |type_name: &str, args: Option<Value>| {
+        def.insert("type".to_string(), json!(type_name));
+        if let Some(args) = args {
+            def.insert("args".to_string(), args);
+        }
+    };
+
+    let mut data_type = &field.data_type;
+    while let ArrowDataType::Dictionary(_, values, _) | ArrowDataType::Extension(_, values, _) = data_type {
+        data_type = values;
+    }
+    match data_type {
+        ArrowDataType::Null => {
+            warnings.push(format!("{}: column is always null, skipped", path));
+            return None;
+        }
+        ArrowDataType::Boolean => set("Boolean", Some(json!({ "ratio": 50 }))),
+        ArrowDataType::Int8 => set("i8", None),
+        ArrowDataType::Int16 => set("i16", None),
+        ArrowDataType::Int32 => set("i32", None),
+        ArrowDataType::Int64 => set("i64", None),
+        ArrowDataType::UInt8 => set("u8", None),
+        ArrowDataType::UInt16 => set("u16", None),
+        ArrowDataType::UInt32 => set("u32", None),
+        ArrowDataType::UInt64 => set("u64", None),
+        ArrowDataType::Float16 | ArrowDataType::Float32 => set("f32", None),
+        ArrowDataType::Float64 => set("f64", None),
+        ArrowDataType::Decimal(precision, scale) | ArrowDataType::Decimal256(precision, scale) => {
+            let (type_name, args) = decimal_type(*precision as u64, *scale as u64);
+            set(type_name, args)
+        }
+        ArrowDataType::Date32 | ArrowDataType::Date64 => set("Date", None),
+        ArrowDataType::Time32(_) | ArrowDataType::Time64(_) => set("Time", None),
+        ArrowDataType::Timestamp(..) => set("DateTime", None),
+        ArrowDataType::Duration(_) | ArrowDataType::Interval(_) => set("Duration", None),
+        ArrowDataType::Utf8 | ArrowDataType::LargeUtf8 | ArrowDataType::Utf8View => set("Word", None),
+        // Parquet's UUID logical type is stored as 16 fixed bytes.
+        ArrowDataType::FixedSizeBinary(16) => set("UUIDv4", None),
+        ArrowDataType::List(items) | ArrowDataType::LargeList(items) | ArrowDataType::FixedSizeList(items, _) => {
+            let items = import_field(items, &format!("{}[]", path), null_ratio, warnings)?;
+            set("Array", None);
+            if let ArrowDataType::FixedSizeList(_, size) = data_type {
+                def.insert("args".to_string(), json!({ "min_items": size, "max_items": size }));
+            }
+            def.insert("items".to_string(), items);
+        }
+        ArrowDataType::Struct(fields) => {
+            let fields: Vec<Value> = fields
+                .iter()
+                .filter_map(|child| import_field(child, &format!("{}.{}", path, child.name), null_ratio, warnings))
+                .collect();
+            if fields.is_empty() {
+                warnings.push(format!("{}: struct has no fields that can be generated, skipped", path));
+                return None;
+            }
+            set("Object", None);
+            def.insert("columns".to_string(), Value::Array(fields));
+            // Struct columns can't be null as a whole.
+            return Some(Value::Object(def));
+        }
+        other => {
+            warnings.push(format!("{}: no generator for {:?}, using Word", path, other));
+            set("Word", None);
+        }
+    }
+    if field.is_nullable && null_ratio > 0.0 {
+        def.insert("null_ratio".to_string(), json!(null_ratio));
+    }
+    Some(Value::Object(def))
+}
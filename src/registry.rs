@@ -0,0 +1,237 @@
+use polars::prelude::*;
+use rand::rngs::StdRng;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::{Arc, OnceLock};
+
+use crate::generate::{GenerateError, RowRng, RowSeeds};
+
+/// Produces the values of one column type.
+///
+/// Rows are generated in chunks, possibly in parallel, and `generate` is
+/// called once per chunk. Call [`RowRng::row`] once at the start of every row
+/// and draw all of that row's randomness from the RNG it returns; that keeps
+/// seeded output reproducible and lets `--anonymize` map equal inputs to
+/// equal fakes.
+///
+/// ```
+/// use polars::prelude::*;
+/// use rand::Rng;
+/// use rsfake::registry::{ColumnGenerator, Registry};
+/// use rsfake::generate::RowRng;
+/// use rsfake::{GenerateError, Generator, Schema};
+///
+/// /// Account numbers like `ACC-0042-7`, ending in a check digit.
+/// struct AccountNumber {
+///     prefix: String,
+/// }
+///
+/// impl ColumnGenerator for AccountNumber {
+///     fn generate(&self, name: &str, rows: usize, rng: &mut RowRng) -> Result<Series, GenerateError> {
+///         let values: Vec<String> = (0..rows)
+///             .map(|_| {
+///                 let number: u32 = rng.row().gen_range(0..10_000);
+///                 format!("{}-{:04}-{}", self.prefix, number, number % 7)
+///             })
+///             .collect();
+///         Ok(Series::new(name, values))
+///     }
+/// }
+///
+/// let mut registry = Registry::default();
+/// registry.register("AccountNumber", |col_def| {
+///     let prefix = col_def["args"]["prefix"].as_str().unwrap_or("ACC").to_string();
+///     Ok(Box::new(AccountNumber { prefix }))
+/// });
+///
+/// let schema = Schema::from_json(r#"{ "columns": [
+///     { "name": "account", "type": "AccountNumber", "args": { "prefix": "ACC" } }
+/// ] }"#)?;
+/// let df = Generator::new(schema).registry(registry).rows(10).generate()?;
+/// assert!(df.column("account")?.str()?.into_no_null_iter().all(|v| v.starts_with("ACC-")));
+/// # Ok::<(), GenerateError>(())
+/// ```
+pub trait ColumnGenerator: Send + Sync {
+    /// Generates `rows` values for the column `name`.
+    fn generate(&self, name: &str, rows: usize, rng: &mut RowRng) -> Result<Series, GenerateError>;
+}
+
+/// Builds a [`ColumnGenerator`] from a column definition, validating its
+/// `args` once rather than per row.
+pub type GeneratorFactory = dyn Fn(&Value) -> Result<Box<dyn ColumnGenerator>, GenerateError> + Send + Sync;
+
+/// Maps the type names used in schemas to generator factories.
+///
+/// [`Registry::default`] holds the built-in types; [`Registry::register`] adds
+/// new ones or replaces built-ins.
+#[derive(Clone)]
+pub struct Registry {
+    factories: HashMap<String, Arc<GeneratorFactory>>,
+}
+
+impl Registry {
+    /// Creates a registry without any types.
+    pub fn empty() -> Self {
+        Registry { factories: HashMap::new() }
+    }
+
+    /// Registers `factory` under `type_name`, replacing any generator already
+    /// registered under that name.
+    ///
+    /// ```
+    /// use rsfake::registry::{string_generator, Registry};
+    ///
+    /// let mut registry = Registry::empty();
+    /// registry.register("Constant", |_| Ok(string_generator(|_, buf| buf.push_str("x"))));
+    /// assert!(registry.contains("Constant"));
+    /// ```
+    pub fn register<F>(&mut self, type_name: impl Into<String>, factory: F) -> &mut Self
+    where
+        F: Fn(&Value) -> Result<Box<dyn ColumnGenerator>, GenerateError> + Send + Sync + 'static,
+    {
+        self.factories.insert(type_name.into(), Arc::new(factory));
+        self
+    }
+
+    /// Whether `type_name` has a registered generator.
+    pub fn contains(&self, type_name: &str) -> bool {
+        self.factories.contains_key(type_name)
+    }
+
+    /// The registered type names, sorted.
+    ///
+    /// ```
+    /// use rsfake::registry::Registry;
+    ///
+    /// assert!(Registry::default().type_names().contains(&"SafeEmail"));
+    /// ```
+    pub fn type_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.factories.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// Builds the generator for `type_name` from the column definition.
+    pub fn build(&self, type_name: &str, col_def: &Value) -> Result<Box<dyn ColumnGenerator>, GenerateError> {
+        let factory = self
+            .factories
+            .get(type_name)
+            .ok_or_else(|| GenerateError::UnsupportedType(type_name.to_string()))?;
+        factory(col_def)
+    }
+
+    /// Generates the column `col_name` of type `type_name`, one value per row
+    /// of `seeds`. With `"as_string": true` the values are cast to strings.
+    pub fn create_series(
+        &self,
+        type_name: &str,
+        col_name: &str,
+        seeds: &RowSeeds,
+        col_def: &Value,
+    ) -> Result<Series, GenerateError> {
+        let generator = self.build(type_name, col_def)?;
+        let series = seeds.generate(col_name, generator.as_ref())?;
+
+        let as_string = col_def.get("as_string").and_then(|v| v.as_bool()).unwrap_or(false);
+        if as_string && series.dtype() != &DataType::String {
+            return Ok(series.cast(&DataType::String)?);
+        }
+        Ok(series)
+    }
+}
+
+impl Default for Registry {
+    /// Creates a registry of the built-in types.
+    fn default() -> Self {
+        builtins().as_ref().clone()
+    }
+}
+
+impl fmt::Debug for Registry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.type_names()).finish()
+    }
+}
+
+/// The shared registry of built-in types.
+pub(crate) fn builtins() -> &'static Arc<Registry> {
+    static BUILTINS: OnceLock<Arc<Registry>> = OnceLock::new();
+    BUILTINS.get_or_init(|| {
+        let mut registry = Registry::empty();
+        crate::builtins::register(&mut registry);
+        Arc::new(registry)
+    })
+}
+
+/// A string generator that has `f` write each value into a reused buffer,
+/// copied straight into an Arrow string builder. Values pushed from a borrowed
+/// `&str` or formatted with `write!` don't allocate per row; a value that `f`
+/// builds as a `String` first still costs that allocation.
+///
+/// ```
+/// use rand::Rng;
+/// use rsfake::registry::{string_generator, Registry};
+///
+/// let mut registry = Registry::empty();
+/// registry.register("Sku", |_| Ok(string_generator(|rng, buf| {
+///     buf.push_str("SKU-");
+///     buf.push_str(&rng.gen_range(100..1000).to_string());
+/// })));
+/// ```
+pub fn string_generator<F>(f: F) -> Box<dyn ColumnGenerator>
+where
+    F: Fn(&mut StdRng, &mut String) + Send + Sync + 'static,
+{
+    Box::new(StringGenerator(f))
+}
+
+/// A generator of values with a native dtype, one call of `f` per row.
+///
+/// ```
+/// use rand::Rng;
+/// use rsfake::registry::{value_generator, Registry};
+///
+/// let mut registry = Registry::empty();
+/// registry.register("Dice", |_| Ok(value_generator(|rng| rng.gen_range(1..=6u8))));
+/// ```
+pub fn value_generator<T, F>(f: F) -> Box<dyn ColumnGenerator>
+where
+    T: 'static,
+    F: Fn(&mut StdRng) -> T + Send + Sync + 'static,
+    Series: NamedFrom<Vec<T>, [T]>,
+{
+    Box::new(ValueGenerator(f, PhantomData))
+}
+
+struct StringGenerator<F>(F);
+
+impl<F> ColumnGenerator for StringGenerator<F>
+where
+    F: Fn(&mut StdRng, &mut String) + Send + Sync,
+{
+    fn generate(&self, name: &str, rows: usize, rng: &mut RowRng) -> Result<Series, GenerateError> {
+        let mut builder = StringChunkedBuilder::new(name, rows);
+        let mut buf = String::new();
+        for _ in 0..rows {
+            buf.clear();
+            (self.0)(rng.row(), &mut buf);
+            builder.append_value(buf.as_str());
+        }
+        Ok(builder.finish().into_series())
+    }
+}
+
+struct ValueGenerator<T, F>(F, PhantomData<fn() -> T>);
+
+impl<T, F> ColumnGenerator for ValueGenerator<T, F>
+where
+    F: Fn(&mut StdRng) -> T + Send + Sync,
+    Series: NamedFrom<Vec<T>, [T]>,
+{
+    fn generate(&self, name: &str, rows: usize, rng: &mut RowRng) -> Result<Series, GenerateError> {
+        let values: Vec<T> = (0..rows).map(|_| (self.0)(rng.row())).collect();
+        Ok(Series::new(name, values))
+    }
+}
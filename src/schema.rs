@@ -0,0 +1,377 @@
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use polars::prelude::DataFrame;
+use serde_json::{json, Map, Value};
+
+use crate::auto::{type_from_name, AutoType};
+use crate::compose;
+use crate::generate::{GenerateError, Generator};
+
+/// A parsed schema: the `columns` to generate plus optional sections such as
+/// `output`.
+///
+/// ```
+/// use rsfake::Schema;
+///
+/// let schema = Schema::from_json(r#"{ "columns": [{ "name": "id", "type": "u32" }] }"#)?;
+/// assert_eq!(schema.columns().len(), 1);
+/// # Ok::<(), rsfake::GenerateError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Schema {
+    json: Value,
+}
+
+impl Schema {
+    /// Parses a schema from JSON text.
+    ///
+    /// ```
+    /// use rsfake::Schema;
+    ///
+    /// assert!(Schema::from_json(r#"{ "columns": [] }"#).is_ok());
+    /// assert!(Schema::from_json(r#"{ "rows": 10 }"#).is_err());
+    /// ```
+    pub fn from_json(json: &str) -> Result<Self, GenerateError> {
+        Self::from_value(serde_json::from_str(json)?)
+    }
+
+    /// Reads and parses a schema file: YAML for `.yaml`/`.yml`, TOML for
+    /// `.toml` and JSON otherwise. `$include`s are read relative to the file,
+    /// and they, `definitions` and `$ref`s are resolved into plain columns.
+    ///
+    /// ```
+    /// use rsfake::Schema;
+    ///
+    /// let schema = Schema::from_path("examples/schema-csv-output.json")?;
+    /// assert!(schema.section("output").is_some());
+    /// let yaml = Schema::from_path("examples/schema-customers.yaml")?;
+    /// let toml = Schema::from_path("examples/schema-customers.toml")?;
+    /// assert_eq!(yaml.as_value(), toml.as_value());
+    ///
+    /// let composed = Schema::from_path("examples/schema-composed.json")?;
+    /// let total = &composed.columns()[2];
+    /// assert_eq!(total["type"], "f64");
+    /// assert_eq!(total["args"]["range"]["end"], 500);
+    /// assert_eq!(composed.columns().last().unwrap()["name"], "created_by");
+    /// assert!(composed.section("definitions").is_none());
+    /// # Ok::<(), rsfake::GenerateError>(())
+    /// ```
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, GenerateError> {
+        let path = path.as_ref();
+        let base_dir = path.parent().unwrap_or(Path::new(""));
+        Self::from_value_in(read_value(path)?, base_dir)
+    }
+
+    /// Parses a schema from JSON, YAML or TOML text.
+    ///
+    /// ```
+    /// use rsfake::schema::{Schema, SchemaFormat};
+    ///
+    /// let schema = Schema::parse(r#"
+    /// ## Loyalty tiers are assigned at signup.
+    /// [[columns]]
+    /// name = "tier"
+    /// type = "Choice"
+    /// args = { values = ["bronze", "silver", "gold"] }
+    /// "#, SchemaFormat::Toml)?;
+    /// assert_eq!(schema.columns()[0]["args"]["values"][2], "gold");
+    /// # Ok::<(), rsfake::GenerateError>(())
+    /// ```
+    pub fn parse(text: &str, format: SchemaFormat) -> Result<Self, GenerateError> {
+        Self::from_value(parse_value(text, format)?)
+    }
+
+    /// Serializes the schema in `format`. Comments in the source aren't kept.
+    ///
+    /// ```
+    /// use rsfake::schema::{Schema, SchemaFormat};
+    ///
+    /// let schema = Schema::from_json(r#"{ "columns": [{ "name": "id", "type": "u32" }] }"#)?;
+    /// assert_eq!(schema.render(SchemaFormat::Yaml)?, "columns:\n- name: id\n  type: u32\n");
+    /// # Ok::<(), rsfake::GenerateError>(())
+    /// ```
+    pub fn render(&self, format: SchemaFormat) -> Result<String, GenerateError> {
+        Ok(match format {
+            SchemaFormat::Json => serde_json::to_string_pretty(&self.json)? + "\n",
+            SchemaFormat::Yaml => serde_yaml::to_string(&self.json)?,
+            SchemaFormat::Toml => toml::to_string_pretty(&self.json)?,
+        })
+    }
+
+    /// Wraps an already parsed JSON document, checking that it has a `columns` array.
+    ///
+    /// ```
+    /// use rsfake::Schema;
+    /// use serde_json::json;
+    ///
+    /// let schema = Schema::from_value(json!({ "columns": [{ "name": "word", "type": "Word" }] }))?;
+    /// assert_eq!(schema.columns()[0]["type"], "Word");
+    /// # Ok::<(), rsfake::GenerateError>(())
+    /// ```
+    pub fn from_value(json: Value) -> Result<Self, GenerateError> {
+        Self::from_value_in(json, Path::new(""))
+    }
+
+    /// Like [`Schema::from_value`], resolving `$include` paths against `base_dir`.
+    fn from_value_in(json: Value, base_dir: &Path) -> Result<Self, GenerateError> {
+        let json = compose::resolve(json, base_dir)?;
+        if !json.get("columns").is_some_and(Value::is_array) {
+            return Err(GenerateError::InvalidArgument(
+                "Missing or invalid 'columns' array in JSON schema".to_string(),
+            ));
+        }
+        Ok(Schema { json })
+    }
+
+    /// The column definitions, in output order.
+    ///
+    /// ```
+    /// use rsfake::Schema;
+    ///
+    /// let schema = Schema::from_json(r#"{ "columns": [{ "name": "a", "type": "u8" }, { "name": "b", "type": "Word" }] }"#)?;
+    /// let names: Vec<&str> = schema.columns().iter().filter_map(|c| c["name"].as_str()).collect();
+    /// assert_eq!(names, ["a", "b"]);
+    /// # Ok::<(), rsfake::GenerateError>(())
+    /// ```
+    pub fn columns(&self) -> &[Value] {
+        self.json["columns"].as_array().map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// A top-level section of the schema, such as `output`.
+    ///
+    /// ```
+    /// use rsfake::Schema;
+    ///
+    /// let schema = Schema::from_json(r#"{ "columns": [], "output": { "compression": "gzip" } }"#)?;
+    /// assert_eq!(schema.section("output").unwrap()["compression"], "gzip");
+    /// assert!(schema.section("missing").is_none());
+    /// # Ok::<(), rsfake::GenerateError>(())
+    /// ```
+    pub fn section(&self, key: &str) -> Option<&Value> {
+        self.json.get(key)
+    }
+
+    /// The whole schema document.
+    pub fn as_value(&self) -> &Value {
+        &self.json
+    }
+
+    /// Picks a type from the name of every column whose `type` is missing or
+    /// `"auto"`, falling back to `Word`, and returns the choices so they can
+    /// be reported and pinned in the schema. Nested `Object` and `Array`
+    /// columns are resolved too, and any `args` already set are kept.
+    ///
+    /// ```
+    /// use rsfake::Schema;
+    ///
+    /// let mut schema = Schema::from_json(r#"{ "columns": [
+    ///     { "name": "user_id" },
+    ///     { "name": "email", "type": "auto" },
+    ///     { "name": "plan", "type": "Word" }
+    /// ] }"#)?;
+    /// let picked = schema.resolve_auto_types();
+    /// assert_eq!(picked.len(), 2);
+    /// assert_eq!(schema.columns()[0]["type"], "Sequence");
+    /// assert_eq!(schema.columns()[1]["type"], "SafeEmail");
+    /// # Ok::<(), rsfake::GenerateError>(())
+    /// ```
+    pub fn resolve_auto_types(&mut self) -> Vec<AutoType> {
+        let mut picked = Vec::new();
+        if let Some(columns) = self.json["columns"].as_array_mut() {
+            for column in columns {
+                resolve_auto_type(column, "", &mut picked);
+            }
+        }
+        picked
+    }
+}
+
+fn resolve_auto_type(column: &mut Value, parent: &str, picked: &mut Vec<AutoType>) {
+    let Some(def) = column.as_object_mut() else {
+        return;
+    };
+    let name = def.get("name").and_then(Value::as_str).unwrap_or_default().to_string();
+    let path = if parent.is_empty() { name.clone() } else { format!("{}.{}", parent, name) };
+
+    if matches!(def.get("type").and_then(Value::as_str), None | Some("" | "auto")) {
+        let found = type_from_name(&name);
+        let (type_name, args) = found.clone().unwrap_or(("Word", None));
+        // Rebuild the column so `type` and `args` follow `name` when it's printed.
+        let mut resolved = Map::new();
+        resolved.insert("name".to_string(), json!(name));
+        resolved.insert("type".to_string(), json!(type_name));
+        let args = match def.shift_remove("args") {
+            Some(own) => {
+                resolved.insert("args".to_string(), own);
+                None
+            }
+            None => {
+                if let Some(args) = &args {
+                    resolved.insert("args".to_string(), args.clone());
+                }
+                args
+            }
+        };
+        for (key, value) in std::mem::take(def) {
+            if key != "type" {
+                resolved.entry(key).or_insert(value);
+            }
+        }
+        *def = resolved;
+        picked.push(AutoType { column: path.clone(), type_name, args, matched: found.is_some() });
+    }
+
+    if let Some(Value::Array(fields)) = def.get_mut("columns") {
+        for field in fields {
+            resolve_auto_type(field, &path, picked);
+        }
+    }
+    if let Some(items) = def.get_mut("items") {
+        resolve_auto_type(items, &path, picked);
+    }
+}
+
+impl FromStr for Schema {
+    type Err = GenerateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Schema::from_json(s)
+    }
+}
+
+/// A schema file format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchemaFormat {
+    Json,
+    Yaml,
+    Toml,
+}
+
+impl SchemaFormat {
+    /// The format implied by a `.json`, `.yaml`/`.yml` or `.toml` extension.
+    ///
+    /// ```
+    /// use rsfake::schema::SchemaFormat;
+    ///
+    /// assert_eq!(SchemaFormat::from_extension("schemas/users.yml"), Some(SchemaFormat::Yaml));
+    /// assert_eq!(SchemaFormat::from_extension("schema"), None);
+    /// ```
+    pub fn from_extension(path: impl AsRef<Path>) -> Option<Self> {
+        let extension = path.as_ref().extension()?.to_str()?;
+        extension.to_ascii_lowercase().parse().ok()
+    }
+
+    /// The usual file extension for the format.
+    pub fn extension(self) -> &'static str {
+        match self {
+            SchemaFormat::Json => "json",
+            SchemaFormat::Yaml => "yaml",
+            SchemaFormat::Toml => "toml",
+        }
+    }
+}
+
+impl FromStr for SchemaFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(SchemaFormat::Json),
+            "yaml" | "yml" => Ok(SchemaFormat::Yaml),
+            "toml" => Ok(SchemaFormat::Toml),
+            _ => Err(format!("unknown schema format '{}', expected json, yaml or toml", s)),
+        }
+    }
+}
+
+/// Reads a schema file without resolving it: YAML for `.yaml`/`.yml`, TOML
+/// for `.toml` and JSON otherwise.
+pub(crate) fn read_value(path: &Path) -> Result<Value, GenerateError> {
+    let format = SchemaFormat::from_extension(path).unwrap_or(SchemaFormat::Json);
+    parse_value(&fs::read_to_string(path)?, format)
+}
+
+fn parse_value(text: &str, format: SchemaFormat) -> Result<Value, GenerateError> {
+    Ok(match format {
+        SchemaFormat::Json => serde_json::from_str(text)?,
+        SchemaFormat::Yaml => serde_yaml::from_str(text)?,
+        SchemaFormat::Toml => toml_to_json(toml::from_str(text)?),
+    })
+}
+
+/// Converts parsed TOML to JSON. TOML dates and times, which have no JSON
+/// equivalent, become RFC 3339 strings as the datetime args expect.
+fn toml_to_json(value: toml::Value) -> Value {
+    match value {
+        toml::Value::String(s) => Value::String(s),
+        toml::Value::Integer(i) => Value::from(i),
+        toml::Value::Float(f) => Value::from(f),
+        toml::Value::Boolean(b) => Value::Bool(b),
+        toml::Value::Datetime(dt) => Value::String(dt.to_string()),
+        toml::Value::Array(values) => Value::Array(values.into_iter().map(toml_to_json).collect()),
+        toml::Value::Table(table) => Value::Object(table.into_iter().map(|(k, v)| (k, toml_to_json(v))).collect()),
+    }
+}
+
+/// A Rust type whose fields map to schema columns, so structs and generated
+/// files can't drift apart. Usually derived:
+///
+/// ```
+/// use fake::Dummy;
+/// use polars::prelude::DataType;
+/// use rsfake::FakeSchema;
+///
+/// #[derive(Dummy, FakeSchema)]
+/// struct User {
+///     #[dummy(faker = "1..10_000")]
+///     id: u32,
+///     #[dummy(faker = "fake::faker::internet::en::SafeEmail()")]
+///     email: String,
+///     #[rsfake(type = "FirstName", null_ratio = 0.2)]
+///     nickname: Option<String>,
+///     #[rsfake(range(start = 18, end = 80))]
+///     age: u32,
+///     #[rsfake(type = "Choice", args = r#"{ "values": ["free", "pro"] }"#)]
+///     plan: String,
+///     active: bool,
+///     level: u8,
+///     tag: String,
+///     #[rsfake(skip)]
+///     #[dummy(default)]
+///     internal: Vec<u8>,
+/// }
+///
+/// let df = User::dataframe(100)?;
+/// assert_eq!(df.get_column_names(), ["id", "email", "nickname", "age", "plan", "active", "level", "tag"]);
+/// assert_eq!(df.column("level").unwrap().dtype(), &DataType::UInt8);
+/// assert_eq!(df.column("tag").unwrap().dtype(), &DataType::String);
+/// # Ok::<(), rsfake::GenerateError>(())
+/// ```
+///
+/// Each field's type comes from `#[rsfake(type = "...")]`, else the faker
+/// named in `#[dummy(faker = "...")]`, else the Rust type (integers, floats,
+/// `bool` and `String` as a `Word`, also inside `Option`). Ranges likewise come from
+/// `#[rsfake(range(start = .., end = ..))]` or a literal dummy range. Other
+/// field attributes are `name = "..."`, `null_ratio = 0.1`, `args = "{json}"`,
+/// `as_string` and `skip`.
+pub trait FakeSchema {
+    /// The schema as JSON text.
+    const SCHEMA: &'static str;
+
+    /// The parsed schema.
+    fn schema() -> Schema {
+        Schema::from_json(Self::SCHEMA).expect("derived schemas are valid")
+    }
+
+    /// A generator for this type's schema.
+    fn generator() -> Generator {
+        Generator::new(Self::schema())
+    }
+
+    /// Generates `rows` random rows.
+    fn dataframe(rows: usize) -> Result<DataFrame, GenerateError> {
+        Self::generator().rows(rows).generate()
+    }
+}